@@ -112,10 +112,164 @@ function accCong(arg1, arg2) {
     return (arg1 === arg2);
 }
 
+// 审计模式：同时计算原生结果和精确结果，返回原生结果，不一致时上报
+// 同一位置的同一运算只上报一次，可通过 accSetAuditReporter 将结果发送到监控平台
+var accAuditReported = {};
+var accAuditReporter = function (info) {
+    console.warn(`计算精度丢失，位置：${info.location}，${info.arg1} ${info.op} ${info.arg2} 原生结果为 ${info.native}，精确结果为 ${info.accurate}`);
+};
+
+function accSetAuditReporter(reporter) {
+    accAuditReporter = reporter;
+}
+
+function accAudit(op, arg1, arg2, native, accurate, location) {
+    var accurateValue;
+    try {
+        accurateValue = accurate(arg1, arg2);
+    }
+    catch (e) {
+        return native;
+    }
+    // 只比较数字结果，NaN 视为一致
+    if (typeof native !== 'number' || native === accurateValue || (native !== native && accurateValue !== accurateValue)) {
+        return native;
+    }
+    // 嵌套的运算可能起始于同一位置，按位置和运算符去重
+    var key = location + op;
+    if (!accAuditReported[key]) {
+        accAuditReported[key] = true;
+        accAuditReporter({ op: op, arg1: arg1, arg2: arg2, native: native, accurate: accurateValue, location: location });
+    }
+    return native;
+}
+
+function accAuditAdd(arg1, arg2, location) {
+    return accAudit('+', arg1, arg2, arg1 + arg2, accAdd, location);
+}
+
+function accAuditSub(arg1, arg2, location) {
+    return accAudit('-', arg1, arg2, arg1 - arg2, accSub, location);
+}
+
+function accAuditMul(arg1, arg2, location) {
+    return accAudit('*', arg1, arg2, arg1 * arg2, accMul, location);
+}
+
+function accAuditDiv(arg1, arg2, location) {
+    return accAudit('/', arg1, arg2, arg1 / arg2, accDiv, location);
+}
+
+// 与插件编译时的日期改写规则保持一致
+var ACC_ISO_DATE = /^\d{4}-\d{2}(-\d{2}(T\d{2}:\d{2}(:\d{2}(\.\d{1,3})?)?(Z|[+-]\d{2}:\d{2})?)?)?$/;
+var ACC_DATE_ONLY = /^(\d{4})-(\d{1,2})(?:-(\d{1,2}))?$/;
+var ACC_DASH_DATE = /^(\d{4})-(\d{1,2})-(\d{1,2})[ T](\d{1,2}:\d{2}(?::\d{2})?)$/;
+var ACC_OFFSET_DATE = /^(\d{4}-\d{2}-\d{2}T\d{2}:\d{2}(?::\d{2}(?:\.\d{1,3})?)?[+-]\d{2})(\d{2})$/;
+
+function accPad(value) {
+    return value.length < 2 ? '0' + value : value;
+}
+
+// semantics 为只有日期的字符串表示的时间：preserve（默认）、local 或 utc
+function accNormalizeDate(value, semantics) {
+    if (typeof value !== 'string') {
+        return value;
+    }
+    var str = value.trim();
+    var match = ACC_DATE_ONLY.exec(str);
+    if (match) {
+        var day = match[3] || '1';
+        if (semantics === 'utc') {
+            return match[1] + '-' + accPad(match[2]) + '-' + accPad(day) + 'T00:00:00Z';
+        }
+        if (semantics !== 'local' && ACC_ISO_DATE.test(str)) {
+            return value;
+        }
+        return match[1] + '/' + match[2] + '/' + day;
+    }
+    if (ACC_ISO_DATE.test(str)) {
+        return value;
+    }
+    match = ACC_DASH_DATE.exec(str);
+    if (match) {
+        return match[1] + '/' + match[2] + '/' + match[3] + ' ' + match[4];
+    }
+    match = ACC_OFFSET_DATE.exec(str);
+    if (match) {
+        return match[1] + ':' + match[2];
+    }
+    return value;
+}
+
+function accDate(value, semantics) {
+    return new Date(accNormalizeDate(value, semantics));
+}
+
+function accDateParse(value, semantics) {
+    return Date.parse(accNormalizeDate(value, semantics));
+}
+
+var ACC_AWAIT_STOP = {};
+
+function accAwait(promise, source, location, stop) {
+    return Promise.resolve(promise).catch(function (error) {
+        console.error(`await ${source} 执行失败，位置：${location}`, error);
+        if (stop) {
+            throw ACC_AWAIT_STOP;
+        }
+    });
+}
+accAwait.stop = ACC_AWAIT_STOP;
+
+// 超出安全范围的整数在解析前加上引号，解析结果为字符串，避免 64 位 id 丢失精度
+function accJsonParse(text, reviver) {
+    if (typeof text !== 'string') {
+        return JSON.parse(text, reviver);
+    }
+    var result = '';
+    var start = 0;
+    var i = 0;
+    while (i < text.length) {
+        var ch = text[i];
+        if (ch === '"') {
+            i++;
+            while (i < text.length && text[i] !== '"') {
+                i += text[i] === '\\' ? 2 : 1;
+            }
+            i++;
+            continue;
+        }
+        if (ch === '-' || (ch >= '0' && ch <= '9')) {
+            var end = i + 1;
+            while (end < text.length && /[0-9.eE+-]/.test(text[end])) {
+                end++;
+            }
+            var token = text.slice(i, end);
+            if (/^-?\d+$/.test(token) && !Number.isSafeInteger(Number(token))) {
+                result += text.slice(start, i) + '"' + token + '"';
+                start = end;
+            }
+            i = end;
+            continue;
+        }
+        i++;
+    }
+    return JSON.parse(result + text.slice(start), reviver);
+}
+
 module.exports = {
     accAdd,
     accSub,
     accMul,
     accDiv,
-    accCong
+    accCong,
+    accAuditAdd,
+    accAuditSub,
+    accAuditMul,
+    accAuditDiv,
+    accSetAuditReporter,
+    accAwait,
+    accDate,
+    accDateParse,
+    accJsonParse
 }
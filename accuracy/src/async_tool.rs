@@ -1,49 +1,244 @@
-use swc_common::{SyntaxContext, DUMMY_SP};
+use std::{collections::HashSet, mem};
+
+use regex::Regex;
+use swc_common::{
+    comments::Comments, errors::SourceMapperDyn, sync::Lrc, util::take::Take, BytePos, Span,
+    Spanned, SyntaxContext, DUMMY_SP,
+};
+use swc_core::{
+    atoms::Atom,
+    ecma::{
+        utils::find_pat_ids,
+        visit::{Visit, VisitMut, VisitMutWith, VisitWith},
+    },
+};
 use swc_ecma_ast::*;
 
-// 帮助函数，检查函数体是否已被包裹
-pub fn already_wrapped(block: &BlockStmt) -> bool {
-    // 检查 block 中是否存在语句，并且第一个语句是否是 TryStmt
-    if let Some(first_stmt) = block.stmts.first() {
-        if let Stmt::Try(_) = first_stmt {
-            // 如果第一个语句是 TryStmt 类型，则返回 true
-            return true;
+use crate::{config::AsyncTryFilter, diagnostic_tool::span_location};
+
+const SAFE_ASYNC_ANNOTATION: &str = "@safe-async";
+
+pub const ACC_AWAIT: &str = "accAwait";
+
+// async 函数所处的上下文，由声明、表达式、箭头函数和方法几条路径共同填充
+#[derive(Clone, Default, Debug)]
+pub struct FnContext {
+    // 函数名，或者函数被赋值到的变量名、属性名
+    pub name: Option<Atom>,
+    // 函数是否被导出
+    pub exported: bool,
+    // 可能携带 @safe-async 注释的位置
+    pub comment_pos: Vec<BytePos>,
+}
+
+// 根据 asyncTryFilter 配置判断 async 函数是否需要包裹
+pub struct AsyncTryMatcher {
+    filter: AsyncTryFilter,
+    name_patterns: Vec<Regex>,
+}
+
+impl AsyncTryMatcher {
+    pub fn new(filter: AsyncTryFilter) -> Self {
+        let name_patterns = filter
+            .name_patterns
+            .iter()
+            .map(|pattern| Regex::new(pattern).expect("Invalid plugin config"))
+            .collect();
+        AsyncTryMatcher {
+            filter,
+            name_patterns,
         }
     }
 
-    // 如果没有找到 TryStmt，返回 false
-    false
+    // 所有开启的条件都满足时才包裹，has_await 只在需要时才计算
+    pub fn matches<C: Comments>(
+        &self,
+        ctx: &FnContext,
+        comments: &C,
+        has_await: impl FnOnce() -> bool,
+    ) -> bool {
+        if self.filter.exported_only && !ctx.exported {
+            return false;
+        }
+        if !self.name_patterns.is_empty() {
+            let matched = ctx.name.as_ref().is_some_and(|name| {
+                self.name_patterns
+                    .iter()
+                    .any(|pattern| pattern.is_match(name))
+            });
+            if !matched {
+                return false;
+            }
+        }
+        if self.filter.annotated_only && !has_annotation(ctx, comments) {
+            return false;
+        }
+        if self.filter.await_only && !has_await() {
+            return false;
+        }
+        true
+    }
 }
 
-pub fn wrap_arrow_body_with_try_catch(node: &mut ArrowExpr) {
-    if node.is_async {
-        match &mut *node.body {
-            // 如果函数体是块状，检查并包裹
-            BlockStmtOrExpr::BlockStmt(block) => {
-                if !already_wrapped(block) {
-                    wrap_with_try_catch(block);
+fn has_annotation<C: Comments>(ctx: &FnContext, comments: &C) -> bool {
+    ctx.comment_pos.iter().any(|pos| {
+        comments.with_leading(*pos, |leading| {
+            leading
+                .iter()
+                .any(|comment| comment.text.contains(SAFE_ASYNC_ANNOTATION))
+        })
+    })
+}
+
+// 查找函数体中属于当前函数的 await，不进入嵌套函数
+#[derive(Default)]
+pub struct AwaitFinder {
+    found: bool,
+    // async 生成器中的 yield 会等待其操作数，同样视为 await
+    count_yield: bool,
+}
+
+impl Visit for AwaitFinder {
+    fn visit_await_expr(&mut self, _: &AwaitExpr) {
+        self.found = true;
+    }
+
+    fn visit_yield_expr(&mut self, n: &YieldExpr) {
+        if self.count_yield {
+            self.found = true;
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_for_of_stmt(&mut self, n: &ForOfStmt) {
+        if n.is_await {
+            self.found = true;
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_function(&mut self, _: &Function) {}
+
+    fn visit_arrow_expr(&mut self, _: &ArrowExpr) {}
+}
+
+pub fn contains_await<N: VisitWith<AwaitFinder>>(node: &N, is_generator: bool) -> bool {
+    let mut finder = AwaitFinder {
+        found: false,
+        count_yield: is_generator,
+    };
+    node.visit_with(&mut finder);
+    finder.found
+}
+
+// 收集通过 export { a, b as c } 和 export default a 导出的本地名称
+pub fn collect_exported_names(module: &Module) -> HashSet<Atom> {
+    let mut names = HashSet::new();
+    for item in &module.body {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                specifiers,
+                src: None,
+                ..
+            })) => {
+                for specifier in specifiers {
+                    if let ExportSpecifier::Named(ExportNamedSpecifier {
+                        orig: ModuleExportName::Ident(ident),
+                        ..
+                    }) = specifier
+                    {
+                        names.insert(ident.sym.clone());
+                    }
                 }
             }
-            // 如果函数体是表达式，转换为块并包裹
-            BlockStmtOrExpr::Expr(expr) => {
-                let block = BlockStmt {
-                    span: DUMMY_SP,
-                    stmts: vec![Stmt::Return(ReturnStmt {
-                        span: DUMMY_SP,
-                        arg: Some(expr.clone()),
-                    })],
-                    ctxt: SyntaxContext::empty(),
-                };
-                let mut wrapped_block = block.clone();
-                wrap_with_try_catch(&mut wrapped_block);
-                *node.body = *Box::new(BlockStmtOrExpr::BlockStmt(wrapped_block));
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(ExportDefaultExpr {
+                expr,
+                ..
+            })) => {
+                if let Expr::Ident(ident) = &**expr {
+                    names.insert(ident.sym.clone());
+                }
             }
+            _ => {}
         }
     }
+    names
 }
-// 帮助函数，用于将函数体包裹在 try-catch 中
-pub fn wrap_with_try_catch(body: &mut BlockStmt) {
-    let console_error_stmt = Stmt::Expr(ExprStmt {
+
+// 判断表达式是否为函数或箭头函数，忽略外层括号
+pub fn is_fn_like(expr: &Expr) -> bool {
+    match expr {
+        Expr::Fn(_) | Expr::Arrow(_) => true,
+        Expr::Paren(ParenExpr { expr, .. }) => is_fn_like(expr),
+        _ => false,
+    }
+}
+
+// 从属性名中取出用于匹配的函数名
+pub fn prop_name(key: &PropName) -> Option<Atom> {
+    match key {
+        PropName::Ident(ident) => Some(ident.sym.clone()),
+        PropName::Str(str) => Some(str.value.clone()),
+        _ => None,
+    }
+}
+
+// 帮助函数，判断语句是否属于指令序言，例如 'use strict'
+fn is_directive(stmt: &Stmt) -> bool {
+    if let Stmt::Expr(ExprStmt { expr, .. }) = stmt {
+        return matches!(&**expr, Expr::Lit(Lit::Str(_)));
+    }
+    false
+}
+
+// 帮助函数，返回函数体开头指令序言的语句数量
+fn directive_len(stmts: &[Stmt]) -> usize {
+    stmts.iter().take_while(|stmt| is_directive(stmt)).count()
+}
+
+// 帮助函数，检查函数体是否已被包裹
+pub fn already_wrapped(block: &BlockStmt) -> bool {
+    // 除指令序言和空语句外，函数体只能是一个带 catch 的 TryStmt
+    let mut stmts = block.stmts[directive_len(&block.stmts)..]
+        .iter()
+        .filter(|stmt| !matches!(stmt, Stmt::Empty(_)));
+    match (stmts.next(), stmts.next()) {
+        (Some(Stmt::Try(try_stmt)), None) => try_stmt.handler.is_some(),
+        _ => false,
+    }
+}
+
+// 帮助函数，将箭头函数的表达式函数体转换为 return 语句组成的块
+pub fn arrow_block_body(node: &mut ArrowExpr) -> &mut BlockStmt {
+    if let BlockStmtOrExpr::Expr(expr) = &mut *node.body {
+        *node.body = BlockStmtOrExpr::BlockStmt(BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![Stmt::Return(ReturnStmt {
+                span: DUMMY_SP,
+                arg: Some(expr.take()),
+            })],
+            ctxt: SyntaxContext::empty(),
+        });
+    }
+    match &mut *node.body {
+        BlockStmtOrExpr::BlockStmt(block) => block,
+        BlockStmtOrExpr::Expr(_) => unreachable!(),
+    }
+}
+
+pub fn wrap_arrow_body_with_try_catch(node: &mut ArrowExpr) {
+    if node.is_async {
+        // 如果函数体是表达式，先转换为块，再检查并包裹
+        let block = arrow_block_body(node);
+        if !already_wrapped(block) {
+            wrap_with_try_catch(block);
+        }
+    }
+}
+
+// 帮助函数，创建 console.error(this, error)
+fn create_report_stmt() -> Stmt {
+    Stmt::Expr(ExprStmt {
         expr: Box::new(Expr::Call(CallExpr {
             span: DUMMY_SP,
             callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
@@ -77,9 +272,13 @@ pub fn wrap_with_try_catch(body: &mut BlockStmt) {
             ctxt: SyntaxContext::empty(),
         })),
         span: DUMMY_SP,
-    });
-    let try_stmt = Stmt::Try(Box::new(TryStmt {
-        block: body.clone(),
+    })
+}
+
+// 帮助函数，创建 try { block } catch (error) { handler }
+fn create_try_catch(block: BlockStmt, handler: Vec<Stmt>) -> Stmt {
+    Stmt::Try(Box::new(TryStmt {
+        block,
         handler: Some(CatchClause {
             param: Some(Pat::Ident(BindingIdent::from(Ident::new(
                 "error".into(),
@@ -88,15 +287,279 @@ pub fn wrap_with_try_catch(body: &mut BlockStmt) {
             )))),
             body: BlockStmt {
                 span: DUMMY_SP,
-                stmts: vec![console_error_stmt],
+                stmts: handler,
                 ctxt: SyntaxContext::empty(), // 如有需要，可以在此处添加自定义的 catch 逻辑
             },
             span: DUMMY_SP,
         }),
         finalizer: None,
         span: DUMMY_SP,
-    }));
+    }))
+}
+
+// 帮助函数，用于将函数体包裹在 try-catch 中
+pub fn wrap_with_try_catch(body: &mut BlockStmt) {
+    // 指令序言需要保留在 try 代码块之外，否则不再生效
+    let stmts = body.stmts.split_off(directive_len(&body.stmts));
+    let try_stmt = create_try_catch(
+        BlockStmt {
+            span: body.span,
+            stmts,
+            ctxt: body.ctxt,
+        },
+        vec![create_report_stmt()],
+    );
 
     // 将原始函数体替换为 try-catch 代码块
-    body.stmts = vec![try_stmt];
+    body.stmts.push(try_stmt);
+}
+
+// 帮助函数，用于 await 模式下 awaitErrorAction 为 return 时包裹函数体
+// try { body } catch (error) { if (error !== accAwait.stop) throw error; }
+pub fn wrap_with_await_stop(body: &mut BlockStmt) {
+    let error = || {
+        Box::new(Expr::Ident(Ident::new(
+            "error".into(),
+            DUMMY_SP,
+            SyntaxContext::empty(),
+        )))
+    };
+    let rethrow_stmt = Stmt::If(IfStmt {
+        span: DUMMY_SP,
+        test: Box::new(Expr::Bin(BinExpr {
+            span: DUMMY_SP,
+            op: BinaryOp::NotEqEq,
+            left: error(),
+            right: Box::new(Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::new(Expr::Ident(Ident::new(
+                    ACC_AWAIT.into(),
+                    DUMMY_SP,
+                    SyntaxContext::empty(),
+                ))),
+                prop: MemberProp::Ident(IdentName::new("stop".into(), DUMMY_SP)),
+            })),
+        })),
+        cons: Box::new(Stmt::Throw(ThrowStmt {
+            span: DUMMY_SP,
+            arg: error(),
+        })),
+        alt: None,
+    });
+    let stmts = body.stmts.split_off(directive_len(&body.stmts));
+    let try_stmt = create_try_catch(
+        BlockStmt {
+            span: body.span,
+            stmts,
+            ctxt: body.ctxt,
+        },
+        vec![rethrow_stmt],
+    );
+    body.stmts.push(try_stmt);
+}
+
+// 逐个改写 await 表达式：await expr => await accAwait(expr, "expr", "file:line:col")
+// 用户 try-catch 中的 await 由用户自行处理，嵌套函数中的 await 属于其他函数，均不改写
+pub struct AwaitRewriter {
+    source_map: Option<Lrc<SourceMapperDyn>>,
+    cwd: Option<String>,
+    stop: bool,
+    pub found: bool,
+}
+
+impl AwaitRewriter {
+    pub fn new(source_map: Option<Lrc<SourceMapperDyn>>, cwd: Option<String>, stop: bool) -> Self {
+        AwaitRewriter {
+            source_map,
+            cwd,
+            stop,
+            found: false,
+        }
+    }
+
+    // await 表达式的源码及其所在的 文件:行:列
+    fn describe(&self, span: Span) -> (String, String) {
+        let source = match &self.source_map {
+            Some(source_map) if !span.is_dummy() => {
+                source_map.span_to_snippet(span).unwrap_or_default()
+            }
+            _ => String::new(),
+        };
+        (
+            source,
+            span_location(&self.source_map, self.cwd.as_deref(), span),
+        )
+    }
+}
+
+impl VisitMut for AwaitRewriter {
+    fn visit_mut_function(&mut self, _: &mut Function) {}
+
+    fn visit_mut_arrow_expr(&mut self, _: &mut ArrowExpr) {}
+
+    fn visit_mut_try_stmt(&mut self, n: &mut TryStmt) {
+        if n.handler.is_none() {
+            n.block.visit_mut_with(self);
+        }
+        n.handler.visit_mut_with(self);
+        n.finalizer.visit_mut_with(self);
+    }
+
+    fn visit_mut_await_expr(&mut self, n: &mut AwaitExpr) {
+        n.visit_mut_children_with(self);
+        let (source, location) = self.describe(n.arg.span());
+        let str_arg = |value: String| ExprOrSpread {
+            spread: None,
+            expr: Box::new(Expr::Lit(Lit::Str(Str {
+                span: DUMMY_SP,
+                value: value.into(),
+                raw: None,
+            }))),
+        };
+        let mut args = vec![
+            ExprOrSpread {
+                spread: None,
+                expr: n.arg.take(),
+            },
+            str_arg(source),
+            str_arg(location),
+        ];
+        if self.stop {
+            args.push(ExprOrSpread {
+                spread: None,
+                expr: Box::new(Expr::Lit(Lit::Bool(Bool {
+                    span: DUMMY_SP,
+                    value: true,
+                }))),
+            });
+        }
+        *n.arg = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
+                ACC_AWAIT.into(),
+                DUMMY_SP,
+                SyntaxContext::empty(),
+            )))),
+            args,
+            type_args: None,
+            ctxt: SyntaxContext::empty(),
+        });
+        self.found = true;
+    }
+}
+
+// 将模块顶层包含 await 的语句逐条包裹在 try-catch 中
+pub fn wrap_top_level_await(items: &mut Vec<ModuleItem>) {
+    for item in mem::take(items) {
+        match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) if contains_await(&*var, false) => {
+                for (decl, assigns) in split_var_decl(*var) {
+                    items.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(decl)))));
+                    if let Some(assigns) = assigns {
+                        items.push(ModuleItem::Stmt(create_try_catch(
+                            block(assigns),
+                            vec![create_report_stmt()],
+                        )));
+                    }
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                span,
+                decl: Decl::Var(var),
+            })) if contains_await(&*var, false) => {
+                for (decl, assigns) in split_var_decl(*var) {
+                    items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                        span,
+                        decl: Decl::Var(Box::new(decl)),
+                    })));
+                    if let Some(assigns) = assigns {
+                        items.push(ModuleItem::Stmt(create_try_catch(
+                            block(assigns),
+                            vec![create_report_stmt()],
+                        )));
+                    }
+                }
+            }
+            // 其他声明移入 try 代码块会改变作用域，保持原样
+            ModuleItem::Stmt(stmt @ Stmt::Decl(_)) => items.push(ModuleItem::Stmt(stmt)),
+            ModuleItem::Stmt(stmt) if contains_await(&stmt, false) => {
+                items.push(ModuleItem::Stmt(create_try_catch(
+                    block(vec![stmt]),
+                    vec![create_report_stmt()],
+                )));
+            }
+            _ => items.push(item),
+        }
+    }
+}
+
+fn block(stmts: Vec<Stmt>) -> BlockStmt {
+    BlockStmt {
+        span: DUMMY_SP,
+        stmts,
+        ctxt: SyntaxContext::empty(),
+    }
+}
+
+// 按声明顺序拆分变量声明：不含 await 的声明符保持原声明（包括 const），
+// 含 await 的 const a = await f() 拆分为 let a; 与放入 try 的 a = await f();，
+// 保证绑定仍在模块作用域。这部分 const 绑定只能改为 let，export const 会变为 export let
+fn split_var_decl(mut var: VarDecl) -> Vec<(VarDecl, Option<Vec<Stmt>>)> {
+    let declarators = mem::take(&mut var.decls);
+    let mut parts: Vec<(VarDecl, Option<Vec<Stmt>>)> = vec![];
+    for declarator in declarators {
+        if !contains_await(&declarator, false) {
+            match parts.last_mut() {
+                Some((decl, None)) => decl.decls.push(declarator),
+                _ => parts.push((
+                    VarDecl {
+                        decls: vec![declarator],
+                        ..var.clone()
+                    },
+                    None,
+                )),
+            }
+            continue;
+        }
+        let ids: Vec<Ident> = find_pat_ids(&declarator.name);
+        let decls = ids
+            .into_iter()
+            .map(|id| VarDeclarator {
+                span: DUMMY_SP,
+                name: Pat::Ident(BindingIdent::from(id)),
+                init: None,
+                definite: false,
+            })
+            .collect();
+        let mut assigns = vec![];
+        if let Some(init) = declarator.init {
+            let left = match declarator.name {
+                Pat::Ident(binding) => AssignTarget::Simple(SimpleAssignTarget::Ident(binding)),
+                Pat::Object(object) => AssignTarget::Pat(AssignTargetPat::Object(object)),
+                Pat::Array(array) => AssignTarget::Pat(AssignTargetPat::Array(array)),
+                _ => continue,
+            };
+            assigns.push(Stmt::Expr(ExprStmt {
+                span: declarator.span,
+                expr: Box::new(Expr::Assign(AssignExpr {
+                    span: DUMMY_SP,
+                    op: AssignOp::Assign,
+                    left,
+                    right: init,
+                })),
+            }));
+        }
+        // var 声明保持 var
+        let kind = match var.kind {
+            VarDeclKind::Const => VarDeclKind::Let,
+            kind => kind,
+        };
+        let decl = VarDecl {
+            kind,
+            decls,
+            ..var.clone()
+        };
+        parts.push((decl, Some(assigns)));
+    }
+    parts
 }
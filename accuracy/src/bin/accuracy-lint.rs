@@ -0,0 +1,113 @@
+// 本地运行数值检查，输出诊断信息，可选写出 SARIF 报告供 CI 标注 PR
+// 用法: accuracy-lint [--level warn|error] [--sarif <path>] <files...>
+use std::{fs, path::Path, process};
+
+use accuracy::{lint_program, sarif_report, DiagnosticLevel, LintFinding};
+use swc_core::{
+    common::{sync::Lrc, SourceMap},
+    ecma::{
+        ast::EsVersion,
+        parser::{parse_file_as_program, EsSyntax, Syntax, TsSyntax},
+    },
+};
+
+fn syntax_for(path: &Path) -> Syntax {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("ts" | "mts" | "cts") => Syntax::Typescript(TsSyntax::default()),
+        Some("tsx") => Syntax::Typescript(TsSyntax {
+            tsx: true,
+            ..Default::default()
+        }),
+        _ => Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+        }),
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("usage: accuracy-lint [--level warn|error] [--sarif <path>] <files...>");
+    process::exit(2);
+}
+
+fn main() {
+    let mut sarif_path = None;
+    let mut level = DiagnosticLevel::Warn;
+    let mut files = vec![];
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--sarif" => sarif_path = Some(args.next().unwrap_or_else(|| usage())),
+            "--level" => {
+                level = match args.next().as_deref() {
+                    Some("warn") => DiagnosticLevel::Warn,
+                    Some("error") => DiagnosticLevel::Error,
+                    _ => usage(),
+                }
+            }
+            _ => files.push(arg),
+        }
+    }
+    if files.is_empty() {
+        usage();
+    }
+
+    let cm: Lrc<SourceMap> = Default::default();
+    let mut reports: Vec<(String, Vec<LintFinding>)> = vec![];
+    let mut failed = false;
+    for file in files {
+        let path = Path::new(&file);
+        let fm = match cm.load_file(path) {
+            Ok(fm) => fm,
+            Err(err) => {
+                eprintln!("{}: {}", file, err);
+                failed = true;
+                continue;
+            }
+        };
+        let program = match parse_file_as_program(
+            &fm,
+            syntax_for(path),
+            EsVersion::latest(),
+            None,
+            &mut vec![],
+        ) {
+            Ok(program) => program,
+            Err(err) => {
+                eprintln!("{}: 解析失败 {:?}", file, err.kind());
+                failed = true;
+                continue;
+            }
+        };
+        let findings = lint_program(&program);
+        for finding in &findings {
+            let loc = cm.lookup_char_pos(finding.span.lo);
+            eprintln!(
+                "{}:{}:{} [{}] {}",
+                file,
+                loc.line,
+                loc.col.0 + 1,
+                finding.rule.id(),
+                finding.rule.message()
+            );
+        }
+        reports.push((file.replace('\\', "/"), findings));
+    }
+
+    if let Some(sarif_path) = sarif_path {
+        let report = sarif_report(&cm, &reports, level);
+        let content = serde_json::to_string_pretty(&report).expect("serialize sarif report");
+        if let Err(err) = fs::write(&sarif_path, content) {
+            eprintln!("{}: {}", sarif_path, err);
+            process::exit(2);
+        }
+    }
+
+    let found = reports.iter().any(|(_, findings)| !findings.is_empty());
+    if failed {
+        process::exit(2);
+    }
+    if found && level == DiagnosticLevel::Error {
+        process::exit(1);
+    }
+}
@@ -1,24 +1,217 @@
 use serde::{Deserialize, Serialize};
-use serde_json;
 use std::fmt::Display;
 
+#[derive(Clone, Default, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AsyncTryFilter {
+    // 只包裹被导出的函数
+    #[serde(default)]
+    pub exported_only: bool,
+    // 只包裹名称匹配任一正则的函数
+    #[serde(default)]
+    pub name_patterns: Vec<String>,
+    // 只包裹函数体内真正使用了 await 的函数
+    #[serde(default)]
+    pub await_only: bool,
+    // 只包裹带有 @safe-async 注释的函数
+    #[serde(default)]
+    pub annotated_only: bool,
+}
+
+// async 函数的处理方式
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum AsyncTryMode {
+    // 将整个函数体包裹在一个 try-catch 中
+    #[default]
+    Body,
+    // 逐个处理 await 表达式，上报出错的表达式源码及位置
+    Await,
+}
+
+// await 模式下 await 出错后的行为
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum AwaitErrorAction {
+    // await 表达式结果为 undefined，继续执行
+    #[default]
+    Continue,
+    // 函数直接返回 undefined
+    Return,
+}
+
+// 只有日期的字符串（例如 2020-01-01）在改写后表示的时间
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum DateOnlySemantics {
+    // 保持原有含义：标准 ISO 日期为 UTC 零点，其他格式为本地零点
+    #[default]
+    Preserve,
+    // 统一为本地零点
+    Local,
+    // 统一为 UTC 零点
+    Utc,
+}
+
+// 诊断信息的级别
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum DiagnosticLevel {
+    #[default]
+    Off,
+    Warn,
+    // 上报为错误，构建失败
+    Error,
+}
+
+// 四则运算的处理方式
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ArithmeticMode {
+    // 改写为精确计算，返回精确结果
+    #[default]
+    Accurate,
+    // 同时计算原生结果和精确结果，返回原生结果，不一致时上报位置
+    Audit,
+}
+
+// 超出 Number.MAX_SAFE_INTEGER 的整数字面量的处理方式
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum UnsafeIntegerAction {
+    #[default]
+    Off,
+    // 只上报警告
+    Warn,
+    // 上报为错误，构建失败
+    Error,
+    // 改为 BigInt 字面量。BigInt 不能与 number 混合运算，id + 1 这类代码运行时会抛出 TypeError
+    #[serde(rename = "bigint")]
+    BigInt,
+    // 改为字符串字面量
+    String,
+}
+
+// 功能开关只在指定的构建环境中开启
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum BuildEnv {
+    Development,
+    Production,
+}
+
+// 功能开关，可选 true、false、"development"、"production"
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum FeatureToggle {
+    Bool(bool),
+    Env(BuildEnv),
+}
+
+impl Default for FeatureToggle {
+    fn default() -> Self {
+        FeatureToggle::Bool(false)
+    }
+}
+
+impl From<bool> for FeatureToggle {
+    fn from(value: bool) -> Self {
+        FeatureToggle::Bool(value)
+    }
+}
+
+impl FeatureToggle {
+    // env 为 swc 的 envName（默认取 NODE_ENV），除 production 外都视为开发环境
+    pub fn is_enabled(self, env: &str) -> bool {
+        match self {
+            FeatureToggle::Bool(value) => value,
+            FeatureToggle::Env(BuildEnv::Production) => env == "production",
+            FeatureToggle::Env(BuildEnv::Development) => env != "production",
+        }
+    }
+}
+
+// 按构建环境取值的配置，可以直接写值，也可以写为 { "development": 值, "production": 值 }，
+// 未配置的环境使用默认值
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged, bound(deserialize = "T: Deserialize<'de> + Default"))]
+pub enum EnvValue<T> {
+    Value(T),
+    Env(EnvValues<T>),
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct EnvValues<T> {
+    #[serde(default)]
+    pub development: T,
+    #[serde(default)]
+    pub production: T,
+}
+
+impl<T: Default> Default for EnvValue<T> {
+    fn default() -> Self {
+        EnvValue::Value(T::default())
+    }
+}
+
+impl<T> From<T> for EnvValue<T> {
+    fn from(value: T) -> Self {
+        EnvValue::Value(value)
+    }
+}
+
+impl<T: Copy> EnvValue<T> {
+    // 与 FeatureToggle 相同，除 production 外都视为开发环境
+    pub fn resolve(self, env: &str) -> T {
+        match self {
+            EnvValue::Value(value) => value,
+            EnvValue::Env(values) if env == "production" => values.production,
+            EnvValue::Env(values) => values.development,
+        }
+    }
+}
+
 #[derive(Clone, Default, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Config {
     #[serde(default, rename = "checkChong")]
-    pub check_chong: bool,
+    pub check_chong: FeatureToggle,
     #[serde(default, rename = "addAsyncTry")]
-    pub add_async_try: bool,
+    pub add_async_try: FeatureToggle,
     #[serde(default, rename = "promiseCatch")]
-    pub promise_catch: bool,
+    pub promise_catch: FeatureToggle,
+    #[serde(default)]
+    pub async_try_filter: AsyncTryFilter,
+    #[serde(default, rename = "topLevelAwaitTry")]
+    pub top_level_await_try: FeatureToggle,
+    #[serde(default, rename = "asyncTryMode")]
+    pub async_try_mode: EnvValue<AsyncTryMode>,
+    #[serde(default, rename = "awaitErrorAction")]
+    pub await_error_action: EnvValue<AwaitErrorAction>,
+    #[serde(default, rename = "promiseDiagnostics")]
+    pub promise_diagnostics: EnvValue<DiagnosticLevel>,
+    #[serde(default, rename = "dateOnlySemantics")]
+    pub date_only_semantics: EnvValue<DateOnlySemantics>,
+    #[serde(default, rename = "numericLint")]
+    pub numeric_lint: EnvValue<DiagnosticLevel>,
+    #[serde(default, rename = "arithmeticMode")]
+    pub arithmetic_mode: EnvValue<ArithmeticMode>,
+    #[serde(default, rename = "pureAnnotations")]
+    pub pure_annotations: FeatureToggle,
+    #[serde(default, rename = "unsafeInteger")]
+    pub unsafe_integer: EnvValue<UnsafeIntegerAction>,
+    #[serde(default, rename = "safeJsonParse")]
+    pub safe_json_parse: FeatureToggle,
 }
 
 impl Config {
     pub fn new(check_chong: bool, add_async_try: bool, promise_catch: bool) -> Config {
         Config {
-            check_chong,
-            add_async_try,
-            promise_catch,
+            check_chong: check_chong.into(),
+            add_async_try: add_async_try.into(),
+            promise_catch: promise_catch.into(),
+            ..Default::default()
         }
     }
 }
@@ -0,0 +1,84 @@
+use swc_common::{
+    errors::{DiagnosticId, SourceMapperDyn, HANDLER},
+    sync::Lrc,
+    Span,
+};
+
+use crate::config::DiagnosticLevel;
+
+// 通过插件的 handler 上报诊断信息，error 级别会使构建失败
+pub fn emit_diagnostic(level: DiagnosticLevel, span: Span, message: &str) {
+    HANDLER.with(|handler| match level {
+        DiagnosticLevel::Off => {}
+        DiagnosticLevel::Warn => handler.struct_span_warn(span, message).emit(),
+        DiagnosticLevel::Error => handler.struct_span_err(span, message).emit(),
+    });
+}
+
+// 带规则 id 的诊断信息，便于按规则过滤
+pub fn emit_lint(level: DiagnosticLevel, span: Span, message: &str, rule_id: &str) {
+    let code = DiagnosticId::Lint(rule_id.to_string());
+    HANDLER.with(|handler| match level {
+        DiagnosticLevel::Off => {}
+        DiagnosticLevel::Warn => handler
+            .struct_span_warn_with_code(span, message, code)
+            .emit(),
+        DiagnosticLevel::Error => handler
+            .struct_span_err_with_code(span, message, code)
+            .emit(),
+    });
+}
+
+// span 所在的 文件:行:列，文件为相对 cwd 的路径，避免把构建机器的绝对路径写入产物
+// 没有 source map 或 span 为空时返回空字符串
+pub fn span_location(
+    source_map: &Option<Lrc<SourceMapperDyn>>,
+    cwd: Option<&str>,
+    span: Span,
+) -> String {
+    match source_map {
+        Some(source_map) if !span.is_dummy() => {
+            let loc = source_map.lookup_char_pos(span.lo);
+            let file = relative_path(&loc.file.name.to_string(), cwd);
+            format!("{}:{}:{}", file, loc.line, loc.col.0 + 1)
+        }
+        _ => String::new(),
+    }
+}
+
+// 统一为 / 分隔并去掉 . 和 .. 路径段，位于 cwd 中的文件转为相对路径
+pub fn relative_path(path: &str, cwd: Option<&str>) -> String {
+    let path = normalize_path(path);
+    if let Some(cwd) = cwd.map(normalize_path) {
+        let prefix = if cwd.ends_with('/') { cwd } else { cwd + "/" };
+        if let Some(relative) = path.strip_prefix(&prefix) {
+            return relative.to_string();
+        }
+    }
+    path
+}
+
+fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    // 保留开头的 / 或 Windows 盘符，盘符统一为小写
+    let (root, rest) = match path.as_bytes() {
+        [drive, b':', ..] if drive.is_ascii_alphabetic() => (
+            format!("{}:/", (*drive as char).to_ascii_lowercase()),
+            path[2..].trim_start_matches('/'),
+        ),
+        [b'/', ..] => ("/".to_string(), path.trim_start_matches('/')),
+        _ => (String::new(), &path[..]),
+    };
+    let mut segments: Vec<&str> = vec![];
+    for segment in rest.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." if matches!(segments.last(), Some(last) if *last != "..") => {
+                segments.pop();
+            }
+            ".." if !root.is_empty() => {}
+            _ => segments.push(segment),
+        }
+    }
+    format!("{}{}", root, segments.join("/"))
+}
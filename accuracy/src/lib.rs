@@ -1,41 +1,117 @@
-use std::vec;
-use swc_core::ecma::atoms::JsWord;
+use std::{collections::HashSet, mem, vec};
 use swc_core::{
+    atoms::Atom,
+    common::{
+        comments::{Comments, NoopComments},
+        errors::SourceMapperDyn,
+        sync::Lrc,
+        Mark, Span, Spanned, SyntaxContext,
+    },
     ecma::{
         ast::*,
-        visit::{as_folder, FoldWith, VisitMut, VisitMutWith},
+        visit::{as_folder, FoldWith, VisitMut, VisitMutWith, VisitWith},
+    },
+    plugin::{
+        metadata::TransformPluginMetadataContextKind, plugin_transform,
+        proxies::TransformPluginProgramMetadata,
     },
-    plugin::{plugin_transform, proxies::TransformPluginProgramMetadata},
 };
 
 mod async_tool;
 mod config;
+mod diagnostic_tool;
+mod lint_tool;
 mod new_date_tool;
+mod number_tool;
 mod opration_tool;
 mod promise_tool;
-use async_tool::{already_wrapped, wrap_arrow_body_with_try_catch, wrap_with_try_catch};
-use config::{parse_config, Config};
-use new_date_tool::create_new_regex_call;
+use async_tool::{
+    already_wrapped, arrow_block_body, collect_exported_names, contains_await, is_fn_like,
+    prop_name, wrap_arrow_body_with_try_catch, wrap_top_level_await, wrap_with_await_stop,
+    wrap_with_try_catch, AsyncTryMatcher, AwaitFinder, AwaitRewriter, FnContext, ACC_AWAIT,
+};
+pub use config::{
+    parse_config, ArithmeticMode, AsyncTryFilter, AsyncTryMode, AwaitErrorAction, BuildEnv, Config,
+    DiagnosticLevel, EnvValue, EnvValues, FeatureToggle, UnsafeIntegerAction,
+};
+pub use diagnostic_tool::relative_path;
+use diagnostic_tool::{emit_diagnostic, emit_lint, span_location};
+pub use lint_tool::{lint_program, sarif_report, LintFinding, LintRule, NumericLinter};
+use new_date_tool::{create_date_helper_call, date_call_args, normalize_date_args};
+use number_tool::{
+    convert_unsafe_integer, is_json_parse, replace_json_parse_callee, unsafe_integer_literal,
+    ACC_JSON_PARSE,
+};
 use opration_tool::{
-    create_assign_expr, create_new_bin_call, create_require_statement, push_assign_cache,
-    push_bin_cache,
+    audit_helper, create_assign_expr, create_new_bin_call, create_require_statement,
+    push_assign_cache, push_bin_cache,
 };
-use promise_tool::{create_new_catch_callee, has_catch};
+use promise_tool::{create_new_catch_callee, is_handled, is_promise_expr, AsyncFnCollector};
 
-pub struct TransformVisitor {
+pub struct TransformVisitor<C: Comments> {
     pub cache: Vec<String>,
 
     pub parse_config: Config,
 
     pub has_polyfill_tag: bool,
+
+    pub comments: C,
+
+    // 用于获取源码片段和行列号，未设置时上报信息为空
+    pub source_map: Option<Lrc<SourceMapperDyn>>,
+
+    // 构建环境，用于判断 "development" / "production" 开关是否开启
+    pub env: String,
+
+    // 项目根目录，写入产物的文件位置为相对该目录的路径
+    pub cwd: Option<String>,
+
+    // resolver 为全局变量添加的 mark，用于区分 fetch、Promise 与同名的本地声明
+    pub unresolved_mark: Option<Mark>,
+
+    async_matcher: AsyncTryMatcher,
+
+    // 通过 export { a } 导出的本地名称
+    exported_names: HashSet<Atom>,
+
+    // 父节点为即将访问的 async 函数准备的上下文，函数路径访问时取走
+    fn_ctx: FnContext,
+
+    // 当前是否位于被导出的 class 中
+    in_exported_class: bool,
+
+    // 本模块中声明的 async 函数
+    async_fns: HashSet<Id>,
 }
 
-impl TransformVisitor {
+impl TransformVisitor<NoopComments> {
     pub fn new() -> Self {
+        TransformVisitor::with_config(Config::new(true, true, true), NoopComments)
+    }
+}
+
+impl Default for TransformVisitor<NoopComments> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Comments> TransformVisitor<C> {
+    pub fn with_config(parse_config: Config, comments: C) -> Self {
         TransformVisitor {
             cache: vec![],
             has_polyfill_tag: false,
-            parse_config: Config::new(true, true, true),
+            async_matcher: AsyncTryMatcher::new(parse_config.async_try_filter.clone()),
+            parse_config,
+            comments,
+            source_map: None,
+            env: "development".to_string(),
+            cwd: None,
+            unresolved_mark: None,
+            exported_names: HashSet::new(),
+            fn_ctx: FnContext::default(),
+            in_exported_class: false,
+            async_fns: HashSet::new(),
         }
     }
 
@@ -44,16 +120,116 @@ impl TransformVisitor {
             self.cache.push(cache)
         }
     }
+
+    fn enabled(&self, toggle: FeatureToggle) -> bool {
+        toggle.is_enabled(&self.env)
+    }
+
+    fn env_value<T: Copy>(&self, value: EnvValue<T>) -> T {
+        value.resolve(&self.env)
+    }
+
+    // 为生成的帮助函数调用添加 /*#__PURE__*/，结果未被使用时压缩工具可以直接删除
+    // 只用于没有副作用的帮助函数，可能抛出异常的 accJsonParse 和用于上报的审计帮助函数不添加
+    fn annotate_pure(&self, expr: &mut Expr) {
+        if !self.enabled(self.parse_config.pure_annotations) {
+            return;
+        }
+        if let Expr::Call(call_expr) = expr {
+            if call_expr.span.is_dummy() {
+                call_expr.span = Span::dummy_with_cmt();
+            }
+            self.comments.add_pure_comment(call_expr.span.lo);
+        }
+    }
+
+    // 审计模式下改用 accAuditXxx，并带上运算所在的 文件:行:列
+    fn arithmetic_helper<'a>(&self, op_call: &'a str, span: Span) -> (&'a str, Option<String>) {
+        if self.env_value(self.parse_config.arithmetic_mode) == ArithmeticMode::Audit {
+            if let Some(helper) = audit_helper(op_call) {
+                return (
+                    helper,
+                    Some(span_location(&self.source_map, self.cwd.as_deref(), span)),
+                );
+            }
+        }
+        (op_call, None)
+    }
+
+    // 为紧接着访问的函数设置上下文，访问结束后清空，避免泄漏到其他函数
+    fn visit_with_fn_ctx<N: VisitMutWith<Self>>(&mut self, node: &mut N, ctx: FnContext) {
+        self.fn_ctx = ctx;
+        node.visit_mut_with(self);
+        self.fn_ctx = FnContext::default();
+    }
+
+    // 声明、表达式、箭头函数和方法共用的包裹判断
+    fn should_wrap_async<N: VisitWith<AwaitFinder>>(
+        &self,
+        ctx: &FnContext,
+        body: &N,
+        is_generator: bool,
+    ) -> bool {
+        self.enabled(self.parse_config.add_async_try)
+            && self
+                .async_matcher
+                .matches(ctx, &self.comments, || contains_await(body, is_generator))
+    }
+
+    // async 生成器同样包裹，yield 位于生成的 try 代码块中，
+    // 出错（包括通过 it.throw() 抛入的错误）时上报并结束迭代
+    fn wrap_async_function(&mut self, function: &mut Function, mut ctx: FnContext) {
+        if !function.is_async {
+            return;
+        }
+        ctx.comment_pos.push(function.span.lo);
+        let is_generator = function.is_generator;
+        if let Some(body) = &mut function.body {
+            if !already_wrapped(body) && self.should_wrap_async(&ctx, &*body, is_generator) {
+                match self.env_value(self.parse_config.async_try_mode) {
+                    AsyncTryMode::Body => wrap_with_try_catch(body),
+                    AsyncTryMode::Await => {
+                        if self.rewrite_awaits(body) && self.stop_on_await_error() {
+                            wrap_with_await_stop(body);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn stop_on_await_error(&self) -> bool {
+        self.env_value(self.parse_config.await_error_action) == AwaitErrorAction::Return
+    }
+
+    // await 模式下改写函数中的 await 表达式，返回是否存在被改写的 await
+    fn rewrite_awaits<N: VisitMutWith<AwaitRewriter>>(&mut self, body: &mut N) -> bool {
+        let mut rewriter = AwaitRewriter::new(
+            self.source_map.clone(),
+            self.cwd.clone(),
+            self.stop_on_await_error(),
+        );
+        body.visit_mut_with(&mut rewriter);
+        if rewriter.found {
+            self.cache_push(ACC_AWAIT.to_string());
+        }
+        rewriter.found
+    }
+
+    fn visit_mut_method(&mut self, function: &mut Function, ctx: FnContext) {
+        self.wrap_async_function(function, ctx);
+        function.visit_mut_children_with(self);
+    }
 }
 
-impl VisitMut for TransformVisitor {
+impl<C: Comments> VisitMut for TransformVisitor<C> {
     fn visit_mut_program(&mut self, program: &mut Program) {
         /*
          * 判断当前文件开头是否存在'calc polyfill'
          * 是则不处理这个文件
          */
         if let Program::Module(module) = program {
-            if let Some(ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. }))) = module.body.get(0) {
+            if let Some(ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. }))) = module.body.first() {
                 if let Expr::Lit(Lit::Str(Str { value, .. })) = &**expr {
                     if value == "calc polyfill" {
                         return;
@@ -61,96 +237,258 @@ impl VisitMut for TransformVisitor {
                 }
             }
         }
+        // 静态检查在改写前进行，保证看到的是源码中的表达式
+        let numeric_lint = self.env_value(self.parse_config.numeric_lint);
+        if numeric_lint != DiagnosticLevel::Off {
+            for finding in lint_program(program) {
+                emit_lint(
+                    numeric_lint,
+                    finding.span,
+                    finding.rule.message(),
+                    finding.rule.id(),
+                );
+            }
+        }
         program.visit_mut_children_with(self);
-        if self.cache.len() > 0 {
-            let new_stmt = create_require_statement(self.cache.clone());
+        if !self.cache.is_empty() {
+            let mut new_stmt = create_require_statement(self.cache.clone());
+            // require 帮助函数没有副作用，未使用的导入可以被删除
+            if let Stmt::Decl(Decl::Var(var_decl)) = &mut new_stmt {
+                if let Some(init) = var_decl
+                    .decls
+                    .iter_mut()
+                    .find_map(|decl| decl.init.as_mut())
+                {
+                    self.annotate_pure(init);
+                }
+            }
             if let Program::Module(module) = program {
                 module.body.insert(0, ModuleItem::Stmt(new_stmt));
             }
         }
     }
 
-    // fn visit_mut_call_expr(&mut self, call_expr: &mut CallExpr) {
-    //     if let Callee::Expr(boxed_callee) = &mut call_expr.callee {
-    //         if let Expr::Member(MemberExpr { prop, .. }) = &mut **boxed_callee {
-    //             if let MemberProp::Ident(IdentName { sym, .. }) = prop {
-    //                 if sym == "then" && !has_catch(call_expr) && self.parse_config.promise_catch {
-    //                     create_new_catch_callee(call_expr);
-    //                     return;
-    //                 }
-    //             }
-    //         }
-    //     }
-    //     call_expr.visit_mut_children_with(self);
-    // }
+    fn visit_mut_expr_stmt(&mut self, n: &mut ExprStmt) {
+        n.visit_mut_children_with(self);
 
-    fn visit_mut_fn_decl(&mut self, node: &mut FnDecl) {
-        if node.function.is_async {
-            if let Some(body) = &mut node.function.body {
-                if !already_wrapped(body) && self.parse_config.add_async_try {
-                    wrap_with_try_catch(body);
+        // 只处理游离的 promise，被 return、await、赋值或作为参数的 promise 由调用方处理
+        let unresolved = self
+            .unresolved_mark
+            .map(|mark| SyntaxContext::empty().apply_mark(mark));
+        if !is_promise_expr(&n.expr, &self.async_fns, unresolved) || is_handled(&n.expr) {
+            return;
+        }
+        // 开启诊断时只上报，不改写代码
+        match self.env_value(self.parse_config.promise_diagnostics) {
+            DiagnosticLevel::Off => {
+                if self.enabled(self.parse_config.promise_catch) {
+                    create_new_catch_callee(&mut n.expr);
                 }
             }
+            level => emit_diagnostic(
+                level,
+                n.expr.span(),
+                "promise 未被 await 且没有处理错误，请使用 await 或添加 .catch()",
+            ),
         }
-        node.visit_mut_children_with(self);
     }
 
-    fn visit_mut_fn_expr(&mut self, node: &mut FnExpr) {
-        if !self.parse_config.add_async_try {
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        self.exported_names = collect_exported_names(module);
+        let mut async_fn_collector = AsyncFnCollector::default();
+        module.visit_with(&mut async_fn_collector);
+        self.async_fns = async_fn_collector.names;
+        module.visit_mut_children_with(self);
+        if self.enabled(self.parse_config.top_level_await_try) {
+            wrap_top_level_await(&mut module.body);
+        }
+    }
+
+    fn visit_mut_export_decl(&mut self, n: &mut ExportDecl) {
+        let ctx = FnContext {
+            exported: true,
+            comment_pos: vec![n.span.lo],
+            ..Default::default()
+        };
+        self.visit_with_fn_ctx(&mut n.decl, ctx);
+    }
+
+    fn visit_mut_export_default_decl(&mut self, n: &mut ExportDefaultDecl) {
+        let ctx = FnContext {
+            exported: true,
+            comment_pos: vec![n.span.lo],
+            ..Default::default()
+        };
+        self.visit_with_fn_ctx(&mut n.decl, ctx);
+    }
+
+    fn visit_mut_export_default_expr(&mut self, n: &mut ExportDefaultExpr) {
+        if !is_fn_like(&n.expr) {
+            n.visit_mut_children_with(self);
             return;
         }
-        if node.function.is_async {
-            if let Some(body) = &mut node.function.body {
-                if !already_wrapped(body) {
-                    wrap_with_try_catch(body);
+        let ctx = FnContext {
+            exported: true,
+            comment_pos: vec![n.span.lo],
+            ..Default::default()
+        };
+        self.visit_with_fn_ctx(&mut n.expr, ctx);
+    }
+
+    fn visit_mut_var_decl(&mut self, n: &mut VarDecl) {
+        let mut ctx = mem::take(&mut self.fn_ctx);
+        ctx.comment_pos.push(n.span.lo);
+        for decl in n.decls.iter_mut() {
+            let init_is_fn = decl.init.as_deref().is_some_and(is_fn_like);
+            match &decl.name {
+                Pat::Ident(BindingIdent { id, .. }) if init_is_fn => {
+                    let decl_ctx = FnContext {
+                        name: Some(id.sym.clone()),
+                        exported: ctx.exported || self.exported_names.contains(&id.sym),
+                        comment_pos: ctx.comment_pos.clone(),
+                    };
+                    self.visit_with_fn_ctx(decl, decl_ctx);
                 }
+                _ => decl.visit_mut_with(self),
             }
         }
+    }
 
-        node.visit_mut_children_with(self);
+    fn visit_mut_class_decl(&mut self, n: &mut ClassDecl) {
+        let ctx = mem::take(&mut self.fn_ctx);
+        let exported = ctx.exported || self.exported_names.contains(&n.ident.sym);
+        let in_exported_class = mem::replace(&mut self.in_exported_class, exported);
+        n.visit_mut_children_with(self);
+        self.in_exported_class = in_exported_class;
     }
 
-    fn visit_mut_arrow_expr(&mut self, node: &mut ArrowExpr) {
-        if !self.parse_config.add_async_try {
+    fn visit_mut_class_expr(&mut self, n: &mut ClassExpr) {
+        let ctx = mem::take(&mut self.fn_ctx);
+        let in_exported_class = mem::replace(&mut self.in_exported_class, ctx.exported);
+        n.visit_mut_children_with(self);
+        self.in_exported_class = in_exported_class;
+    }
+
+    fn visit_mut_class_method(&mut self, n: &mut ClassMethod) {
+        n.key.visit_mut_with(self);
+        let ctx = FnContext {
+            name: prop_name(&n.key),
+            exported: self.in_exported_class,
+            comment_pos: vec![n.span.lo],
+        };
+        self.visit_mut_method(&mut n.function, ctx);
+    }
+
+    fn visit_mut_private_method(&mut self, n: &mut PrivateMethod) {
+        let ctx = FnContext {
+            name: Some(n.key.name.clone()),
+            exported: self.in_exported_class,
+            comment_pos: vec![n.span.lo],
+        };
+        self.visit_mut_method(&mut n.function, ctx);
+    }
+
+    fn visit_mut_method_prop(&mut self, n: &mut MethodProp) {
+        n.key.visit_mut_with(self);
+        let ctx = FnContext {
+            name: prop_name(&n.key),
+            comment_pos: vec![n.key.span_lo()],
+            ..Default::default()
+        };
+        self.visit_mut_method(&mut n.function, ctx);
+    }
+
+    fn visit_mut_key_value_prop(&mut self, n: &mut KeyValueProp) {
+        if !is_fn_like(&n.value) {
+            n.visit_mut_children_with(self);
             return;
         }
-        wrap_arrow_body_with_try_catch(node);
+        let ctx = FnContext {
+            name: prop_name(&n.key),
+            comment_pos: vec![n.key.span_lo()],
+            ..Default::default()
+        };
+        n.key.visit_mut_with(self);
+        self.visit_with_fn_ctx(&mut n.value, ctx);
+    }
+
+    fn visit_mut_class_prop(&mut self, n: &mut ClassProp) {
+        if !n.value.as_deref().is_some_and(is_fn_like) {
+            n.visit_mut_children_with(self);
+            return;
+        }
+        let ctx = FnContext {
+            name: prop_name(&n.key),
+            exported: self.in_exported_class,
+            comment_pos: vec![n.span.lo],
+        };
+        n.key.visit_mut_with(self);
+        n.decorators.visit_mut_with(self);
+        self.visit_with_fn_ctx(&mut n.value, ctx);
+    }
+
+    fn visit_mut_fn_decl(&mut self, node: &mut FnDecl) {
+        let mut ctx = mem::take(&mut self.fn_ctx);
+        ctx.exported = ctx.exported || self.exported_names.contains(&node.ident.sym);
+        ctx.name = Some(node.ident.sym.clone());
+        self.wrap_async_function(&mut node.function, ctx);
+        node.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_fn_expr(&mut self, node: &mut FnExpr) {
+        let mut ctx = mem::take(&mut self.fn_ctx);
+        if ctx.name.is_none() {
+            ctx.name = node.ident.as_ref().map(|ident| ident.sym.clone());
+        }
+        self.wrap_async_function(&mut node.function, ctx);
         node.visit_mut_children_with(self);
     }
 
-    fn visit_mut_new_expr(&mut self, n: &mut NewExpr) {
-        // 检查是否是 new Date()
-        if let Expr::Ident(ident) = &*n.callee {
-            if ident.sym == JsWord::new("Date") {
-                // 检查第一个参数是否存在并且是字符串字面量
-                if let Some(arg) = n.args.as_mut() {
-                    if let Some(first_arg) = arg.first_mut() {
-                        if let Expr::Lit(Lit::Str(Str { value, .. })) = &mut *first_arg.expr {
-                            let value_clone = value.clone();
-                            create_new_regex_call(&mut first_arg.expr, value_clone);
-                        }
+    fn visit_mut_arrow_expr(&mut self, node: &mut ArrowExpr) {
+        let mut ctx = mem::take(&mut self.fn_ctx);
+        ctx.comment_pos.push(node.span.lo);
+        if node.is_async && self.should_wrap_async(&ctx, &*node.body, false) {
+            match self.env_value(self.parse_config.async_try_mode) {
+                AsyncTryMode::Body => wrap_arrow_body_with_try_catch(node),
+                AsyncTryMode::Await => {
+                    let wrapped = match &*node.body {
+                        BlockStmtOrExpr::BlockStmt(block) => already_wrapped(block),
+                        BlockStmtOrExpr::Expr(_) => false,
+                    };
+                    if !wrapped
+                        && self.rewrite_awaits(&mut *node.body)
+                        && self.stop_on_await_error()
+                    {
+                        wrap_with_await_stop(arrow_block_body(node));
                     }
                 }
             }
         }
-        // 继续遍历其他节点
-        n.visit_mut_children_with(self);
+        node.visit_mut_children_with(self);
     }
 
     fn visit_mut_assign_expr(&mut self, assign_expr: &mut AssignExpr) {
         let replace_operator = push_assign_cache(&assign_expr.op);
 
         if replace_operator != "None" {
-            if !self.parse_config.check_chong && replace_operator == "accCong" {
+            if !self.enabled(self.parse_config.check_chong) && replace_operator == "accCong" {
                 return;
             }
-            self.cache_push((*replace_operator).to_string());
+            let (helper, location) = self.arithmetic_helper(replace_operator, assign_expr.span);
+            // 审计帮助函数会上报不一致，不能被删除
+            let audited = location.is_some();
+            self.cache_push(helper.to_string());
             create_assign_expr(
                 assign_expr.left.clone(),
                 assign_expr.right.clone(),
                 assign_expr,
-                &replace_operator,
+                helper,
+                location,
             );
+            // 解构等无法改写的赋值保持原有运算符
+            if assign_expr.op == AssignOp::Assign && !audited {
+                self.annotate_pure(&mut assign_expr.right);
+            }
         }
 
         // Continue visiting the node
@@ -158,20 +496,73 @@ impl VisitMut for TransformVisitor {
     }
 
     fn visit_mut_expr(&mut self, expr: &mut Expr) {
+        // 在子节点被改写之前判断 new Date(...) 和 Date.parse(...) 的参数类型
+        let semantics = self.env_value(self.parse_config.date_only_semantics);
+        let date_helper = date_call_args(expr)
+            .and_then(|(helper, args)| normalize_date_args(args, semantics).then_some(helper));
+
+        // 不安全的整数字面量不再包含需要处理的子节点，直接上报或替换
+        let unsafe_integer = self.env_value(self.parse_config.unsafe_integer);
+        if unsafe_integer != UnsafeIntegerAction::Off {
+            if let Some((value, negative, span)) = unsafe_integer_literal(expr) {
+                let message = "整数字面量超出 Number.MAX_SAFE_INTEGER，运行时会丢失精度";
+                match unsafe_integer {
+                    UnsafeIntegerAction::Warn => {
+                        emit_diagnostic(DiagnosticLevel::Warn, span, message)
+                    }
+                    UnsafeIntegerAction::Error => {
+                        emit_diagnostic(DiagnosticLevel::Error, span, message)
+                    }
+                    _ => {
+                        if let Some(new_expr) =
+                            convert_unsafe_integer(value, negative, span, unsafe_integer)
+                        {
+                            *expr = new_expr;
+                        }
+                    }
+                }
+                return;
+            }
+        }
+
         expr.visit_mut_children_with(self);
 
+        if let Some(helper) = date_helper {
+            if let Some((_, args)) = date_call_args(expr) {
+                let args = mem::take(args);
+                self.cache_push(helper.to_string());
+                *expr = create_date_helper_call(helper, args, semantics);
+                self.annotate_pure(expr);
+            }
+            return;
+        }
+
+        if self.enabled(self.parse_config.safe_json_parse) && is_json_parse(expr) {
+            if let Expr::Call(call_expr) = expr {
+                self.cache_push(ACC_JSON_PARSE.to_string());
+                replace_json_parse_callee(call_expr);
+            }
+            // accJsonParse 可能抛出异常，不添加 PURE 注释
+            return;
+        }
+
         if let Expr::Bin(bin_expr) = expr {
             let op = bin_expr.op;
             let new_op_call = push_bin_cache(&op);
             if new_op_call != "None" {
-                if !self.parse_config.check_chong && new_op_call == "accCong" {
+                if !self.enabled(self.parse_config.check_chong) && new_op_call == "accCong" {
                     return;
                 }
-                self.cache_push((&new_op_call).to_string());
+                let (helper, location) = self.arithmetic_helper(new_op_call, bin_expr.span);
+                let audited = location.is_some();
+                self.cache_push(helper.to_string());
                 // 创建一个函数调用表达式来替换二元表达式
-                let new_expr = create_new_bin_call(new_op_call, bin_expr);
+                let new_expr = create_new_bin_call(helper, bin_expr, location);
                 // 替换原有的二元表达式
                 *expr = new_expr;
+                if !audited {
+                    self.annotate_pure(expr);
+                }
             }
         }
     }
@@ -184,13 +575,13 @@ pub fn process_transform(program: Program, _metadata: TransformPluginProgramMeta
             .get_transform_plugin_config()
             .expect("load plugin config failed"),
     );
-    program.fold_with(&mut as_folder(TransformVisitor {
-        cache: vec![],
-        has_polyfill_tag: false,
-        parse_config: Config {
-            add_async_try: parse_config.add_async_try,
-            promise_catch: parse_config.promise_catch,
-            check_chong: parse_config.check_chong,
-        },
-    }))
+    let env = _metadata.get_context(&TransformPluginMetadataContextKind::Env);
+    let mut visitor = TransformVisitor::with_config(parse_config, _metadata.comments);
+    visitor.cwd = _metadata.get_context(&TransformPluginMetadataContextKind::Cwd);
+    visitor.unresolved_mark = Some(_metadata.unresolved_mark);
+    visitor.source_map = Some(Lrc::new(_metadata.source_map));
+    if let Some(env) = env {
+        visitor.env = env;
+    }
+    program.fold_with(&mut as_folder(visitor))
 }
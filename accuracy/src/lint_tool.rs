@@ -0,0 +1,245 @@
+use serde_json::{json, Value};
+use swc_common::{SourceMap, Span};
+use swc_core::ecma::visit::{Visit, VisitWith};
+use swc_ecma_ast::*;
+
+use crate::config::DiagnosticLevel;
+
+// 数值相关的静态检查规则，只上报不改写
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LintRule {
+    NanComparison,
+    FloatEquality,
+    DivisionByZero,
+    ParseIntRadix,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 4] = [
+        LintRule::NanComparison,
+        LintRule::FloatEquality,
+        LintRule::DivisionByZero,
+        LintRule::ParseIntRadix,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            LintRule::NanComparison => "nan-comparison",
+            LintRule::FloatEquality => "float-equality",
+            LintRule::DivisionByZero => "division-by-zero",
+            LintRule::ParseIntRadix => "parseint-radix",
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            LintRule::NanComparison => "与 NaN 比较永远不相等，请使用 Number.isNaN()",
+            LintRule::FloatEquality => {
+                "浮点数运算结果不能直接比较相等，请比较差值是否小于 Number.EPSILON"
+            }
+            LintRule::DivisionByZero => "除数或取模的数为字面量 0，结果为 Infinity 或 NaN",
+            LintRule::ParseIntRadix => "parseInt 缺少第二个参数 radix，请显式指定进制",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LintFinding {
+    pub rule: LintRule,
+    pub span: Span,
+}
+
+#[derive(Default)]
+pub struct NumericLinter {
+    pub findings: Vec<LintFinding>,
+}
+
+impl NumericLinter {
+    fn report(&mut self, rule: LintRule, span: Span) {
+        self.findings.push(LintFinding { rule, span });
+    }
+}
+
+fn is_equality(op: BinaryOp) -> bool {
+    matches!(
+        op,
+        BinaryOp::EqEq | BinaryOp::NotEq | BinaryOp::EqEqEq | BinaryOp::NotEqEq
+    )
+}
+
+fn is_arithmetic(op: BinaryOp) -> bool {
+    matches!(
+        op,
+        BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod
+    )
+}
+
+// NaN 或 Number.NaN
+fn is_nan(expr: &Expr) -> bool {
+    match expr {
+        Expr::Ident(ident) => &*ident.sym == "NaN",
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => matches!(&**obj, Expr::Ident(obj) if &*obj.sym == "Number") && &*prop.sym == "NaN",
+        Expr::Paren(ParenExpr { expr, .. }) => is_nan(expr),
+        _ => false,
+    }
+}
+
+fn is_zero(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(Lit::Num(Number { value, .. })) => *value == 0.0,
+        Expr::Unary(UnaryExpr {
+            op: UnaryOp::Minus | UnaryOp::Plus,
+            arg,
+            ..
+        }) => is_zero(arg),
+        Expr::Paren(ParenExpr { expr, .. }) => is_zero(expr),
+        _ => false,
+    }
+}
+
+fn is_arithmetic_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Bin(BinExpr { op, .. }) => is_arithmetic(*op),
+        Expr::Paren(ParenExpr { expr, .. }) => is_arithmetic_expr(expr),
+        _ => false,
+    }
+}
+
+// 表达式（含算术子表达式）中是否出现小数字面量
+fn has_float_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(Lit::Num(Number { value, .. })) => value.fract() != 0.0,
+        Expr::Unary(UnaryExpr { arg, .. }) => has_float_literal(arg),
+        Expr::Paren(ParenExpr { expr, .. }) => has_float_literal(expr),
+        Expr::Bin(BinExpr {
+            op, left, right, ..
+        }) if is_arithmetic(*op) => has_float_literal(left) || has_float_literal(right),
+        _ => false,
+    }
+}
+
+// parseInt(x) 或 Number.parseInt(x)
+fn is_parse_int(callee: &Callee) -> bool {
+    let Callee::Expr(callee) = callee else {
+        return false;
+    };
+    match &**callee {
+        Expr::Ident(ident) => &*ident.sym == "parseInt",
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => {
+            matches!(&**obj, Expr::Ident(obj) if &*obj.sym == "Number") && &*prop.sym == "parseInt"
+        }
+        _ => false,
+    }
+}
+
+impl Visit for NumericLinter {
+    fn visit_bin_expr(&mut self, n: &BinExpr) {
+        if is_equality(n.op) {
+            if is_nan(&n.left) || is_nan(&n.right) {
+                self.report(LintRule::NanComparison, n.span);
+            } else if (is_arithmetic_expr(&n.left) || is_arithmetic_expr(&n.right))
+                && (has_float_literal(&n.left) || has_float_literal(&n.right))
+            {
+                // 只检查带小数的运算结果，x === 0.5 这类直接比较不上报
+                self.report(LintRule::FloatEquality, n.span);
+            }
+        }
+        if matches!(n.op, BinaryOp::Div | BinaryOp::Mod) && is_zero(&n.right) {
+            self.report(LintRule::DivisionByZero, n.span);
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_assign_expr(&mut self, n: &AssignExpr) {
+        if matches!(n.op, AssignOp::DivAssign | AssignOp::ModAssign) && is_zero(&n.right) {
+            self.report(LintRule::DivisionByZero, n.span);
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, n: &CallExpr) {
+        if is_parse_int(&n.callee) && n.args.len() == 1 {
+            self.report(LintRule::ParseIntRadix, n.span);
+        }
+        n.visit_children_with(self);
+    }
+}
+
+pub fn lint_program(program: &Program) -> Vec<LintFinding> {
+    let mut linter = NumericLinter::default();
+    program.visit_with(&mut linter);
+    linter.findings
+}
+
+fn sarif_level(level: DiagnosticLevel) -> &'static str {
+    match level {
+        DiagnosticLevel::Off => "none",
+        DiagnosticLevel::Warn => "warning",
+        DiagnosticLevel::Error => "error",
+    }
+}
+
+// 生成 SARIF 2.1.0 报告，files 为 (文件路径, 检查结果)，路径原样写入 artifactLocation.uri
+// 列号按字符计算，因此 columnKind 为 unicodeCodePoints（SARIF 默认为 utf16CodeUnits）
+pub fn sarif_report(
+    cm: &SourceMap,
+    files: &[(String, Vec<LintFinding>)],
+    level: DiagnosticLevel,
+) -> Value {
+    let rules: Vec<Value> = LintRule::ALL
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.id(),
+                "shortDescription": { "text": rule.message() },
+            })
+        })
+        .collect();
+    let results: Vec<Value> = files
+        .iter()
+        .flat_map(|(uri, findings)| {
+            findings.iter().map(move |finding| {
+                let lo = cm.lookup_char_pos(finding.span.lo);
+                let hi = cm.lookup_char_pos(finding.span.hi);
+                json!({
+                    "ruleId": finding.rule.id(),
+                    "level": sarif_level(level),
+                    "message": { "text": finding.rule.message() },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": uri },
+                            "region": {
+                                "startLine": lo.line,
+                                "startColumn": lo.col.0 + 1,
+                                "endLine": hi.line,
+                                "endColumn": hi.col.0 + 1,
+                            },
+                        },
+                    }],
+                })
+            })
+        })
+        .collect();
+    json!({
+        "version": "2.1.0",
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "swc-plugin-accuracy",
+                    "rules": rules,
+                },
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results,
+        }],
+    })
+}
@@ -1,42 +1,160 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
 use swc_common::{SyntaxContext, DUMMY_SP};
-use swc_core::atoms::Atom;
 use swc_ecma_ast::*;
 
-pub fn create_new_regex_call(expr: &mut Box<Expr>, value: Atom) {
-    // 创建替换操作: "str".replace(/-/g, '/')
-    let replace_call = Expr::Call(CallExpr {
-        span: DUMMY_SP,
-        callee: Callee::Expr(Box::new(Expr::Member(swc_core::ecma::ast::MemberExpr {
-            span: DUMMY_SP,
-            obj: Box::new(Expr::Lit(Lit::Str(Str {
-                value: value.clone(),
+use crate::config::DateOnlySemantics;
+
+pub const ACC_DATE: &str = "accDate";
+pub const ACC_DATE_PARSE: &str = "accDateParse";
+
+// 标准 ISO 格式，各端都能正确解析，不做处理
+// 例如 2020-01、2020-01-01、2020-01-01T10:00、2020-01-01T10:00:00.000Z、2020-01-01T10:00:00+08:00
+fn iso_regex() -> &'static Regex {
+    static ISO: OnceLock<Regex> = OnceLock::new();
+    ISO.get_or_init(|| {
+        Regex::new(
+            r"^\d{4}-\d{2}(-\d{2}(T\d{2}:\d{2}(:\d{2}(\.\d{1,3})?)?(Z|[+-]\d{2}:\d{2})?)?)?$",
+        )
+        .unwrap()
+    })
+}
+
+// 只有日期的格式，例如 2020-01、2020-01-01、1982-12-2
+fn date_only_regex() -> &'static Regex {
+    static DATE_ONLY: OnceLock<Regex> = OnceLock::new();
+    DATE_ONLY.get_or_init(|| Regex::new(r"^(\d{4})-(\d{1,2})(?:-(\d{1,2}))?$").unwrap())
+}
+
+// Safari 和小程序不支持的 横杠日期 + 时间 格式，例如 2020-01-01 10:00
+fn dash_regex() -> &'static Regex {
+    static DASH: OnceLock<Regex> = OnceLock::new();
+    DASH.get_or_init(|| {
+        Regex::new(r"^(\d{4})-(\d{1,2})-(\d{1,2})[ T](\d{1,2}:\d{2}(?::\d{2})?)$").unwrap()
+    })
+}
+
+// 时区偏移缺少冒号的 ISO 格式，例如 2020-01-01T10:00:00+0800
+fn offset_regex() -> &'static Regex {
+    static OFFSET: OnceLock<Regex> = OnceLock::new();
+    OFFSET.get_or_init(|| {
+        Regex::new(r"^(\d{4}-\d{2}-\d{2}T\d{2}:\d{2}(?::\d{2}(?:\.\d{1,3})?)?[+-]\d{2})(\d{2})$")
+            .unwrap()
+    })
+}
+
+// 解析 new Date 的字符串参数，只改写不兼容的格式，返回 None 表示保持原样
+pub fn normalize_date_literal(value: &str, semantics: DateOnlySemantics) -> Option<String> {
+    let value = value.trim();
+    if let Some(caps) = date_only_regex().captures(value) {
+        let (year, month) = (&caps[1], &caps[2]);
+        let day = caps.get(3).map_or("1", |day| day.as_str());
+        return match semantics {
+            // 标准 ISO 日期按 UTC 解析，保持原样；其他格式按本地时间解析，替换为斜杠后含义不变
+            DateOnlySemantics::Preserve if iso_regex().is_match(value) => None,
+            DateOnlySemantics::Preserve | DateOnlySemantics::Local => {
+                Some(format!("{}/{}/{}", year, month, day))
+            }
+            DateOnlySemantics::Utc => Some(format!("{}-{:0>2}-{:0>2}T00:00:00Z", year, month, day)),
+        };
+    }
+    if iso_regex().is_match(value) {
+        return None;
+    }
+    if let Some(caps) = dash_regex().captures(value) {
+        // 不带时区的时间按本地时间解析，替换为斜杠后含义不变
+        return Some(format!(
+            "{}/{}/{} {}",
+            &caps[1], &caps[2], &caps[3], &caps[4]
+        ));
+    }
+    if let Some(caps) = offset_regex().captures(value) {
+        return Some(format!("{}:{}", &caps[1], &caps[2]));
+    }
+    None
+}
+
+// 返回 new Date(...) 或 Date.parse(...) 的参数，以及参数需要在运行时处理时使用的帮助函数
+pub fn date_call_args(expr: &mut Expr) -> Option<(&'static str, &mut Vec<ExprOrSpread>)> {
+    match expr {
+        Expr::New(NewExpr {
+            callee,
+            args: Some(args),
+            ..
+        }) if is_ident(callee, "Date") => Some((ACC_DATE, args)),
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            args,
+            ..
+        }) => match &**callee {
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(IdentName { sym, .. }),
+                ..
+            }) if is_ident(obj, "Date") && sym == "parse" => Some((ACC_DATE_PARSE, args)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// 处理只有一个参数的 Date 调用：字符串字面量在编译时改写，
+// 返回 true 表示参数可能是运行时才能确定的字符串，需要交给帮助函数处理
+pub fn normalize_date_args(args: &mut [ExprOrSpread], semantics: DateOnlySemantics) -> bool {
+    let [ExprOrSpread { spread: None, expr }] = args else {
+        return false;
+    };
+    match &mut **expr {
+        Expr::Lit(Lit::Str(str)) => {
+            // 直接替换为兼容格式的字面量
+            if let Some(value) = normalize_date_literal(&str.value, semantics) {
+                str.value = value.into();
+                str.raw = None;
+            }
+            false
+        }
+        // 数字等字面量、一元运算、非加法的二元运算以及 new 表达式不会得到字符串
+        Expr::Lit(_) | Expr::Unary(_) | Expr::Update(_) | Expr::New(_) => false,
+        Expr::Bin(BinExpr { op, .. }) => *op == BinaryOp::Add,
+        _ => true,
+    }
+}
+
+// 创建 accDate(arg) 或 accDateParse(arg) 调用，非默认的日期语义作为第二个参数传入
+pub fn create_date_helper_call(
+    helper: &str,
+    mut args: Vec<ExprOrSpread>,
+    semantics: DateOnlySemantics,
+) -> Expr {
+    let semantics = match semantics {
+        DateOnlySemantics::Preserve => None,
+        DateOnlySemantics::Local => Some("local"),
+        DateOnlySemantics::Utc => Some("utc"),
+    };
+    if let Some(semantics) = semantics {
+        args.push(ExprOrSpread {
+            spread: None,
+            expr: Box::new(Expr::Lit(Lit::Str(Str {
                 span: DUMMY_SP,
+                value: semantics.into(),
                 raw: None,
             }))),
-            prop: MemberProp::Ident(IdentName::new("replace".into(), DUMMY_SP)),
-        }))),
-        args: vec![
-            ExprOrSpread {
-                spread: None,
-                expr: Box::new(Expr::Lit(Lit::Regex(Regex {
-                    exp: "-".into(),
-                    flags: "g".into(),
-                    span: DUMMY_SP,
-                }))),
-            },
-            ExprOrSpread {
-                spread: None,
-                expr: Box::new(Expr::Lit(Lit::Str(Str {
-                    value: "/".into(),
-                    span: DUMMY_SP,
-                    raw: None,
-                }))),
-            },
-        ],
+        });
+    }
+    Expr::Call(CallExpr {
+        span: DUMMY_SP,
+        callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
+            helper.into(),
+            DUMMY_SP,
+            SyntaxContext::empty(),
+        )))),
+        args,
         type_args: None,
         ctxt: SyntaxContext::empty(),
-    });
+    })
+}
 
-    // 将第一个参数替换为 .replace(/-/g, '/')
-    *expr = Box::new(replace_call);
+fn is_ident(expr: &Expr, name: &str) -> bool {
+    matches!(expr, Expr::Ident(Ident { sym, .. }) if sym == name)
 }
@@ -0,0 +1,109 @@
+use swc_common::{Span, SyntaxContext, DUMMY_SP};
+use swc_ecma_ast::*;
+
+use crate::config::UnsafeIntegerAction;
+
+pub const ACC_JSON_PARSE: &str = "accJsonParse";
+
+// Number.MAX_SAFE_INTEGER
+const MAX_SAFE_INTEGER: f64 = 9007199254740991.0;
+
+// 超出安全范围的整数字面量，返回源码中写出的精确值
+// 只处理十进制、十六进制、八进制和二进制整数，1e21 这类指数写法视为有意使用浮点数
+// 非严格模式下 0777 这类以 0 开头的旧式八进制（以及 089）不处理
+pub fn unsafe_integer_value(num: &Number) -> Option<BigIntValue> {
+    if num.value.abs() <= MAX_SAFE_INTEGER {
+        return None;
+    }
+    let raw = num.raw.as_ref()?.replace('_', "");
+    if raw.len() > 1 && raw.starts_with('0') && raw.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (digits, radix) = match raw.get(..2) {
+        Some("0x" | "0X") => (&raw[2..], 16),
+        Some("0o" | "0O") => (&raw[2..], 8),
+        Some("0b" | "0B") => (&raw[2..], 2),
+        _ if raw.bytes().all(|b| b.is_ascii_digit()) => (&raw[..], 10),
+        _ => return None,
+    };
+    BigIntValue::parse_bytes(digits.as_bytes(), radix)
+}
+
+// 不安全的整数字面量，包括 -9007199254740993 这类取负的写法，返回精确值、是否取负及位置
+pub fn unsafe_integer_literal(expr: &Expr) -> Option<(BigIntValue, bool, Span)> {
+    match expr {
+        Expr::Lit(Lit::Num(num)) => unsafe_integer_value(num).map(|value| (value, false, num.span)),
+        Expr::Unary(UnaryExpr {
+            op: UnaryOp::Minus,
+            arg,
+            span,
+        }) => match &**arg {
+            Expr::Lit(Lit::Num(num)) => unsafe_integer_value(num).map(|value| (value, true, *span)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// 按配置把不安全的整数字面量替换为 BigInt 或字符串，返回 None 表示保持原样
+pub fn convert_unsafe_integer(
+    value: BigIntValue,
+    negative: bool,
+    span: Span,
+    action: UnsafeIntegerAction,
+) -> Option<Expr> {
+    match action {
+        UnsafeIntegerAction::BigInt => {
+            let lit = Expr::Lit(Lit::BigInt(BigInt {
+                span,
+                value: Box::new(value),
+                raw: None,
+            }));
+            if !negative {
+                return Some(lit);
+            }
+            Some(Expr::Unary(UnaryExpr {
+                span,
+                op: UnaryOp::Minus,
+                arg: Box::new(lit),
+            }))
+        }
+        UnsafeIntegerAction::String => {
+            let value = if negative { -value } else { value };
+            Some(Expr::Lit(Lit::Str(Str {
+                span,
+                value: value.to_string().into(),
+                raw: None,
+            })))
+        }
+        _ => None,
+    }
+}
+
+// JSON.parse(...) 调用
+pub fn is_json_parse(expr: &Expr) -> bool {
+    let Expr::Call(CallExpr {
+        callee: Callee::Expr(callee),
+        ..
+    }) = expr
+    else {
+        return false;
+    };
+    matches!(
+        &**callee,
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if matches!(&**obj, Expr::Ident(obj) if &*obj.sym == "JSON") && &*prop.sym == "parse"
+    )
+}
+
+// JSON.parse(text, reviver) 改为 accJsonParse(text, reviver)
+pub fn replace_json_parse_callee(call_expr: &mut CallExpr) {
+    call_expr.callee = Callee::Expr(Box::new(Expr::Ident(Ident::new(
+        ACC_JSON_PARSE.into(),
+        DUMMY_SP,
+        SyntaxContext::empty(),
+    ))));
+}
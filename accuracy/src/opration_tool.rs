@@ -1,7 +1,23 @@
 use swc_common::{SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::*;
 
-pub fn create_new_bin_call(new_op_call: &str, bin_expr: &mut BinExpr) -> Expr {
+// 审计模式下，location 为编译时写入的 文件:行:列，作为最后一个参数传入
+fn location_arg(location: Option<String>) -> Option<ExprOrSpread> {
+    location.map(|location| ExprOrSpread {
+        spread: None,
+        expr: Box::new(Expr::Lit(Lit::Str(Str {
+            span: DUMMY_SP,
+            value: location.into(),
+            raw: None,
+        }))),
+    })
+}
+
+pub fn create_new_bin_call(
+    new_op_call: &str,
+    bin_expr: &mut BinExpr,
+    location: Option<String>,
+) -> Expr {
     Expr::Call(CallExpr {
         span: DUMMY_SP,
         callee: Callee::Expr(Box::new(Expr::Ident(Ident::new(
@@ -18,7 +34,10 @@ pub fn create_new_bin_call(new_op_call: &str, bin_expr: &mut BinExpr) -> Expr {
                 spread: None,
                 expr: Box::new(*bin_expr.right.clone()),
             },
-        ],
+        ]
+        .into_iter()
+        .chain(location_arg(location))
+        .collect(),
         type_args: None,
         ctxt: SyntaxContext::empty(),
     })
@@ -29,6 +48,7 @@ pub fn create_assign_expr(
     right_expr: Box<Expr>,
     assign_expr: &mut AssignExpr,
     op: &str,
+    location: Option<String>,
 ) {
     let new_left_expr = match &left_expr {
         AssignTarget::Simple(simple_target) => match simple_target {
@@ -77,12 +97,15 @@ pub fn create_assign_expr(
                 spread: None,
                 expr: right_expr.clone(),
             },
-        ],
+        ]
+        .into_iter()
+        .chain(location_arg(location))
+        .collect(),
         type_args: None,
         ctxt: SyntaxContext::empty(),
     });
     // Update the assignment expression
-    assign_expr.right = Box::new(new_right);
+    *assign_expr.right = new_right;
     assign_expr.op = AssignOp::Assign;
 }
 
@@ -155,3 +178,14 @@ pub fn push_bin_cache(op: &BinaryOp) -> &'static str {
         _ => "None",
     }
 }
+
+// 审计模式下使用的帮助函数，=== 不涉及精度，返回 None 保持原有处理
+pub fn audit_helper(op_call: &str) -> Option<&'static str> {
+    match op_call {
+        "accAdd" => Some("accAuditAdd"),
+        "accSub" => Some("accAuditSub"),
+        "accMul" => Some("accAuditMul"),
+        "accDiv" => Some("accAuditDiv"),
+        _ => None,
+    }
+}
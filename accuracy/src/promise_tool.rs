@@ -1,39 +1,156 @@
-use swc_common::{SyntaxContext, DUMMY_SP};
+use std::collections::HashSet;
+
+use swc_common::{util::take::Take, SyntaxContext, DUMMY_SP};
+use swc_core::{
+    atoms::Atom,
+    ecma::visit::{Visit, VisitWith},
+};
 use swc_ecma_ast::*;
 
-pub fn has_catch(call_expr: &mut CallExpr) -> bool {
+// 返回链式调用中当前一环的方法名，例如 a.then(f) 返回 then
+pub fn chain_method(call_expr: &CallExpr) -> Option<&Atom> {
+    if let Callee::Expr(boxed_callee) = &call_expr.callee {
+        if let Expr::Member(MemberExpr {
+            prop: MemberProp::Ident(IdentName { sym, .. }),
+            ..
+        }) = &**boxed_callee
+        {
+            return Some(sym);
+        }
+    }
+    None
+}
+
+// 判断是否为 promise 链上的一环: then、catch 或 finally
+pub fn is_chain_link(call_expr: &CallExpr) -> bool {
+    matches!(
+        chain_method(call_expr).map(|sym| &**sym),
+        Some("then" | "catch" | "finally")
+    )
+}
+
+// 从最外层的调用开始检查，跳过只会透传错误的 finally，
+// 最外层为 catch(h) 或 then(onOk, onErr) 时，链上的错误已经被处理
+pub fn has_catch(call_expr: &CallExpr) -> bool {
     let mut current_expr = call_expr;
-    while let Some(next_expr) = get_next_call_expr(current_expr) {
-        if let Callee::Expr(boxed_callee) = &next_expr.callee {
-            if let Expr::Member(MemberExpr { prop, .. }) = &**boxed_callee {
-                if let MemberProp::Ident(IdentName { sym, .. }) = prop {
-                    if sym == "catch" {
-                        return true;
+    loop {
+        match chain_method(current_expr).map(|sym| &**sym) {
+            Some("catch") => return true,
+            Some("then") => return current_expr.args.len() >= 2,
+            Some("finally") => match get_next_call_expr(current_expr) {
+                Some(next_expr) => current_expr = next_expr,
+                None => return false,
+            },
+            _ => return false,
+        }
+    }
+}
+
+// 判断表达式是否会产生 promise：then 调用链、Promise.all/allSettled/race/any、
+// fetch(...)、new Promise(...) 以及本模块中声明的 async 函数的调用
+// fetch 与 Promise 必须是未被本地声明覆盖的全局变量，unresolved 为 None 时只比较名称
+pub fn is_promise_expr(
+    expr: &Expr,
+    async_fns: &HashSet<Id>,
+    unresolved: Option<SyntaxContext>,
+) -> bool {
+    match expr {
+        Expr::Paren(ParenExpr { expr, .. }) => is_promise_expr(expr, async_fns, unresolved),
+        Expr::New(NewExpr { callee, .. }) => is_global(callee, "Promise", unresolved),
+        Expr::Call(call_expr) if is_chain_link(call_expr) => {
+            if chain_method(call_expr).is_some_and(|sym| sym == "then") {
+                return true;
+            }
+            match &call_expr.callee {
+                Callee::Expr(boxed_callee) => match &**boxed_callee {
+                    Expr::Member(MemberExpr { obj, .. }) => {
+                        is_promise_expr(obj, async_fns, unresolved)
                     }
-                }
+                    _ => false,
+                },
+                _ => false,
             }
         }
-        current_expr = next_expr;
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            ..
+        }) => match &**callee {
+            Expr::Ident(ident) => {
+                is_global(callee, "fetch", unresolved) || async_fns.contains(&ident.to_id())
+            }
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(IdentName { sym, .. }),
+                ..
+            }) => {
+                is_global(obj, "Promise", unresolved)
+                    && matches!(&**sym, "all" | "allSettled" | "race" | "any")
+            }
+            _ => false,
+        },
+        _ => false,
     }
-    false
 }
 
-pub fn get_next_call_expr(call_expr: &mut CallExpr) -> Option<&mut CallExpr> {
-    if let Callee::Expr(boxed_callee) = &mut call_expr.callee {
-        if let Expr::Member(MemberExpr { obj, prop, .. }) = &mut **boxed_callee {
-            if let MemberProp::Ident(IdentName { sym, .. }) = prop {
-                if sym == "then" {
-                    if let Expr::Call(next_call_expr) = &mut **obj {
-                        return Some(next_call_expr);
-                    }
-                }
+// 判断 promise 表达式的错误是否已经被处理
+pub fn is_handled(expr: &Expr) -> bool {
+    match expr {
+        Expr::Paren(ParenExpr { expr, .. }) => is_handled(expr),
+        Expr::Call(call_expr) => has_catch(call_expr),
+        _ => false,
+    }
+}
+
+fn is_global(expr: &Expr, name: &str, unresolved: Option<SyntaxContext>) -> bool {
+    matches!(expr, Expr::Ident(Ident { sym, ctxt, .. })
+        if sym == name && unresolved.is_none_or(|unresolved| *ctxt == unresolved))
+}
+
+// 收集本模块中声明的 async 函数，按 Id 区分同名的局部变量和参数
+#[derive(Default)]
+pub struct AsyncFnCollector {
+    pub names: HashSet<Id>,
+}
+
+impl Visit for AsyncFnCollector {
+    fn visit_fn_decl(&mut self, n: &FnDecl) {
+        if n.function.is_async {
+            self.names.insert(n.ident.to_id());
+        }
+        n.visit_children_with(self);
+    }
+
+    fn visit_var_declarator(&mut self, n: &VarDeclarator) {
+        if let (Pat::Ident(BindingIdent { id, .. }), Some(init)) = (&n.name, &n.init) {
+            let is_async = match &**init {
+                Expr::Arrow(ArrowExpr { is_async, .. }) => *is_async,
+                Expr::Fn(FnExpr { function, .. }) => function.is_async,
+                _ => false,
+            };
+            if is_async {
+                self.names.insert(id.to_id());
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
+
+// 返回链式调用中内层的调用，例如 a.then(f).finally(g) 返回 a.then(f)
+pub fn get_next_call_expr(call_expr: &CallExpr) -> Option<&CallExpr> {
+    if !is_chain_link(call_expr) {
+        return None;
+    }
+    if let Callee::Expr(boxed_callee) = &call_expr.callee {
+        if let Expr::Member(MemberExpr { obj, .. }) = &**boxed_callee {
+            if let Expr::Call(next_call_expr) = &**obj {
+                return Some(next_call_expr);
             }
         }
     }
     None
 }
 
-pub fn create_new_catch_callee(call_expr: &mut CallExpr) {
+pub fn create_new_catch_callee(expr: &mut Expr) {
     let console_error_stmt = Stmt::Expr(ExprStmt {
         expr: Box::new(Expr::Call(CallExpr {
             span: DUMMY_SP,
@@ -85,7 +202,7 @@ pub fn create_new_catch_callee(call_expr: &mut CallExpr) {
         span: DUMMY_SP,
         callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
             span: DUMMY_SP,
-            obj: Box::new(Expr::Call(call_expr.clone())),
+            obj: Box::new(expr.take()),
             prop: MemberProp::Ident(IdentName::new("catch".into(), DUMMY_SP)),
         }))),
         args: vec![ExprOrSpread {
@@ -96,5 +213,5 @@ pub fn create_new_catch_callee(call_expr: &mut CallExpr) {
         ctxt: SyntaxContext::empty(),
     };
 
-    *call_expr = new_func;
+    *expr = Expr::Call(new_func);
 }
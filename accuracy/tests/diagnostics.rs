@@ -0,0 +1,140 @@
+use accuracy::{parse_config, relative_path, TransformVisitor};
+use swc_common::{errors::Level, FileName, SourceMapper};
+use swc_core::{
+    common::{comments::NoopComments, errors::HANDLER},
+    ecma::{
+        ast::Program,
+        parser::{parse_file_as_module, parse_file_as_script, Syntax},
+        visit::VisitMutWith,
+    },
+};
+use testing::Tester;
+
+// 收集转换过程中上报的诊断信息：(级别, 信息, 原始代码片段)
+fn diagnostics(config: &str, code: &str) -> Vec<(Level, String, String)> {
+    collect_diagnostics(config, code, false)
+}
+
+// 以非严格模式的 script 解析，用于模块中不允许的写法
+fn script_diagnostics(config: &str, code: &str) -> Vec<(Level, String, String)> {
+    collect_diagnostics(config, code, true)
+}
+
+fn collect_diagnostics(config: &str, code: &str, script: bool) -> Vec<(Level, String, String)> {
+    let tester = Tester::new();
+    let errors = tester
+        .errors(|cm, handler| {
+            let fm = cm.new_source_file(FileName::Anon.into(), code.to_string());
+            let syntax = Syntax::Es(Default::default());
+            let mut program = if script {
+                Program::Script(
+                    parse_file_as_script(&fm, syntax, Default::default(), None, &mut vec![])
+                        .unwrap(),
+                )
+            } else {
+                Program::Module(
+                    parse_file_as_module(&fm, syntax, Default::default(), None, &mut vec![])
+                        .unwrap(),
+                )
+            };
+            let mut visitor = TransformVisitor::with_config(parse_config(config), NoopComments);
+            visitor.source_map = Some(cm.clone());
+            HANDLER.set(&handler, || program.visit_mut_with(&mut visitor));
+            Err::<(), ()>(())
+        })
+        .unwrap_err();
+    errors
+        .iter()
+        .map(|diagnostic| {
+            let span = diagnostic.span.primary_span().unwrap();
+            (
+                diagnostic.level,
+                diagnostic.message(),
+                tester.cm.span_to_snippet(span).unwrap(),
+            )
+        })
+        .collect()
+}
+
+const FLOATING: &str = "
+function load() {
+    fetch('/api');
+    Promise.all([save(1), save(2)]).then(done);
+    save(3).catch(report);
+    return fetch('/data');
+}
+";
+
+const MESSAGE: &str = "promise 未被 await 且没有处理错误，请使用 await 或添加 .catch()";
+
+#[test]
+fn promise_diagnostics_warn() {
+    let diagnostics = diagnostics(
+        r#"{ "promiseCatch": true, "promiseDiagnostics": "warn" }"#,
+        FLOATING,
+    );
+    assert_eq!(
+        diagnostics,
+        vec![
+            (
+                Level::Warning,
+                MESSAGE.to_string(),
+                "fetch('/api')".to_string()
+            ),
+            (
+                Level::Warning,
+                MESSAGE.to_string(),
+                "Promise.all([save(1), save(2)]).then(done)".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn promise_diagnostics_off() {
+    let diagnostics = diagnostics(r#"{ "promiseCatch": true }"#, FLOATING);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn unsafe_integer_skips_legacy_octal() {
+    let diagnostics = script_diagnostics(
+        r#"{ "unsafeInteger": "warn" }"#,
+        "var id = 12345678901234567890;\nvar legacy = 07777777777777777777;\nvar decimal = 08999999999999999999;\n",
+    );
+    assert_eq!(
+        diagnostics
+            .into_iter()
+            .map(|(_, _, snippet)| snippet)
+            .collect::<Vec<_>>(),
+        vec!["12345678901234567890".to_string()]
+    );
+}
+
+#[test]
+fn relative_paths() {
+    let cases = [
+        ("/project/src/index.js", Some("/project"), "src/index.js"),
+        (
+            "/project/./src/a/../index.js",
+            Some("/project/"),
+            "src/index.js",
+        ),
+        (
+            "C:\\project\\src\\index.js",
+            Some("c:\\project"),
+            "src/index.js",
+        ),
+        ("/other/index.js", Some("/project"), "/other/index.js"),
+        (
+            "/project-b/index.js",
+            Some("/project"),
+            "/project-b/index.js",
+        ),
+        ("/project/src/index.js", None, "/project/src/index.js"),
+        ("input.js", Some("/project"), "input.js"),
+    ];
+    for (path, cwd, expected) in cases {
+        assert_eq!(relative_path(path, cwd), expected, "{} in {:?}", path, cwd);
+    }
+}
@@ -1,16 +1,51 @@
-use std::path::PathBuf;
+use std::{fs, path::PathBuf};
 
-use accuracy::TransformVisitor;
-use swc_core::ecma::{transforms::testing::test_fixture, visit::as_folder};
+use accuracy::{parse_config, Config, TransformVisitor};
+use swc_core::{
+    common::{chain, Mark},
+    ecma::{
+        transforms::{
+            base::resolver,
+            testing::{test_fixture, FixtureTestConfig},
+        },
+        visit::as_folder,
+    },
+};
 
 #[testing::fixture("tests/fixture/**/input.js")]
 fn fixture(input: PathBuf) {
-    let output = input.parent().unwrap().join("output.js");
+    let dir = input.parent().unwrap();
+    let output = dir.join("output.js");
+    // 目录下存在 config.json 时使用其中的插件配置，否则开启全部功能
+    let config = fs::read_to_string(dir.join("config.json"))
+        .map(|config| parse_config(&config))
+        .unwrap_or_else(|_| Config::new(true, true, true));
+    // 目录下存在 env 文件时作为构建环境，否则为 development
+    let env = fs::read_to_string(dir.join("env"))
+        .map(|env| env.trim().to_string())
+        .ok();
     test_fixture(
         Default::default(),
-        &|_t| as_folder(TransformVisitor::new()),
+        &|t| {
+            // 与插件运行时一致，先由 resolver 为标识符标记作用域
+            let unresolved_mark = Mark::new();
+            let mut visitor = TransformVisitor::with_config(config.clone(), t.comments.clone());
+            visitor.source_map = Some(t.cm.clone());
+            visitor.unresolved_mark = Some(unresolved_mark);
+            if let Some(env) = &env {
+                visitor.env = env.clone();
+            }
+            chain!(
+                resolver(unresolved_mark, Mark::new(), false),
+                as_folder(visitor)
+            )
+        },
         &input,
         &output,
-        Default::default(),
+        FixtureTestConfig {
+            // 诊断信息与 output.stderr 比较
+            allow_error: true,
+            ..Default::default()
+        },
     );
 }
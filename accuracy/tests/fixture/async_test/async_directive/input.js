@@ -0,0 +1,13 @@
+async function printFile(filename) {
+    'use strict';
+    let contents = await fs.readFileAsync(filename, 'utf8');
+    console.log(contents);
+}
+async function readFile(filename) {
+    'use strict';
+    try {
+        return await fs.readFileAsync(filename, 'utf8');
+    } catch (error) {
+        console.error(error);
+    }
+}
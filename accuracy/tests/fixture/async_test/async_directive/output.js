@@ -0,0 +1,17 @@
+async function printFile(filename) {
+    'use strict';
+    try {
+        let contents = await fs.readFileAsync(filename, 'utf8');
+        console.log(contents);
+    } catch (error) {
+        console.error(this, error);
+    }
+}
+async function readFile(filename) {
+    'use strict';
+    try {
+        return await fs.readFileAsync(filename, 'utf8');
+    } catch (error) {
+        console.error(error);
+    }
+}
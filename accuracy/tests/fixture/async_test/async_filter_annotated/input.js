@@ -0,0 +1,15 @@
+/** @safe-async */
+async function load(id) {
+    await api.get(id);
+}
+// @safe-async
+const save = async (data) => {
+    await api.post(data);
+};
+/** @safe-async */
+export async function remove(id) {
+    await api.delete(id);
+}
+async function refresh() {
+    await api.refresh();
+}
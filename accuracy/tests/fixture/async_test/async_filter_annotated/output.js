@@ -0,0 +1,27 @@
+/** @safe-async */
+async function load(id) {
+    try {
+        await api.get(id);
+    } catch (error) {
+        console.error(this, error);
+    }
+}
+// @safe-async
+const save = async (data) => {
+    try {
+        await api.post(data);
+    } catch (error) {
+        console.error(this, error);
+    }
+};
+/** @safe-async */
+export async function remove(id) {
+    try {
+        await api.delete(id);
+    } catch (error) {
+        console.error(this, error);
+    }
+}
+async function refresh() {
+    await api.refresh();
+}
@@ -0,0 +1,11 @@
+async function load(id) {
+    return await api.get(id);
+}
+async function stream(source) {
+    for await (const chunk of source) {
+        write(chunk);
+    }
+}
+async function run() {
+    return api.get(1).then(async (res) => await parse(res));
+}
@@ -0,0 +1,25 @@
+async function load(id) {
+    try {
+        return await api.get(id);
+    } catch (error) {
+        console.error(this, error);
+    }
+}
+async function stream(source) {
+    try {
+        for await (const chunk of source) {
+            write(chunk);
+        }
+    } catch (error) {
+        console.error(this, error);
+    }
+}
+async function run() {
+    return api.get(1).then(async (res) => {
+        try {
+            return await parse(res);
+        } catch (error) {
+            console.error(this, error);
+        }
+    });
+}
@@ -0,0 +1,13 @@
+export async function load(id) {
+    await api.get(id);
+}
+export const save = async (data) => {
+    await api.post(data);
+};
+async function remove(id) {
+    await api.delete(id);
+}
+async function refresh() {
+    await api.refresh();
+}
+export { refresh };
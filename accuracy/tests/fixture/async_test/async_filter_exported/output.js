@@ -0,0 +1,25 @@
+export async function load(id) {
+    try {
+        await api.get(id);
+    } catch (error) {
+        console.error(this, error);
+    }
+}
+export const save = async (data) => {
+    try {
+        await api.post(data);
+    } catch (error) {
+        console.error(this, error);
+    }
+};
+async function remove(id) {
+    await api.delete(id);
+}
+async function refresh() {
+    try {
+        await api.refresh();
+    } catch (error) {
+        console.error(this, error);
+    }
+}
+export { refresh };
@@ -0,0 +1,17 @@
+async function handleClick(event) {
+    await submit(event);
+}
+const onLoad = async () => {
+    await init();
+};
+const page = {
+    onShow: async function () {
+        await refresh();
+    },
+    async loadData() {
+        await fetchData();
+    }
+};
+async function helper() {
+    await work();
+}
@@ -0,0 +1,29 @@
+async function handleClick(event) {
+    try {
+        await submit(event);
+    } catch (error) {
+        console.error(this, error);
+    }
+}
+const onLoad = async () => {
+    try {
+        await init();
+    } catch (error) {
+        console.error(this, error);
+    }
+};
+const page = {
+    onShow: async function () {
+        try {
+            await refresh();
+        } catch (error) {
+            console.error(this, error);
+        }
+    },
+    async loadData() {
+        await fetchData();
+    }
+};
+async function helper() {
+    await work();
+}
@@ -0,0 +1,14 @@
+async function* pages(api) {
+    let page = 0;
+    while (true) {
+        yield api.fetchPage(page++);
+    }
+}
+async function* lines(stream) {
+    for await (const chunk of stream) {
+        yield* chunk.split('\n');
+    }
+}
+function* ids() {
+    yield 1;
+}
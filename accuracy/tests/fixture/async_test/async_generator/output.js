@@ -0,0 +1,22 @@
+async function* pages(api) {
+    try {
+        let page = 0;
+        while (true) {
+            yield api.fetchPage(page++);
+        }
+    } catch (error) {
+        console.error(this, error);
+    }
+}
+async function* lines(stream) {
+    try {
+        for await (const chunk of stream) {
+            yield* chunk.split('\n');
+        }
+    } catch (error) {
+        console.error(this, error);
+    }
+}
+function* ids() {
+    yield 1;
+}
@@ -0,0 +1,13 @@
+class Store {
+    async load(id) {
+        return await api.get(id);
+    }
+    async #save(data) {
+        await api.post(data);
+    }
+}
+const service = {
+    async fetch(id) {
+        return await api.get(id);
+    }
+};
@@ -0,0 +1,25 @@
+class Store {
+    async load(id) {
+        try {
+            return await api.get(id);
+        } catch (error) {
+            console.error(this, error);
+        }
+    }
+    async #save(data) {
+        try {
+            await api.post(data);
+        } catch (error) {
+            console.error(this, error);
+        }
+    }
+}
+const service = {
+    async fetch(id) {
+        try {
+            return await api.get(id);
+        } catch (error) {
+            console.error(this, error);
+        }
+    }
+};
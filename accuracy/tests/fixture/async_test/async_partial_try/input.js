@@ -0,0 +1,15 @@
+async function load(url) {
+    try {
+        await fetch(url);
+    } catch (error) {
+        console.error(error);
+    }
+    await save(url);
+}
+async function release(lock) {
+    try {
+        await lock.acquire();
+    } finally {
+        lock.release();
+    }
+}
@@ -0,0 +1,23 @@
+async function load(url) {
+    try {
+        try {
+            await fetch(url);
+        } catch (error) {
+            console.error(error);
+        }
+        await save(url);
+    } catch (error) {
+        console.error(this, error);
+    }
+}
+async function release(lock) {
+    try {
+        try {
+            await lock.acquire();
+        } finally {
+            lock.release();
+        }
+    } catch (error) {
+        console.error(this, error);
+    }
+}
@@ -0,0 +1,10 @@
+async function load(id) {
+    const user = await api.getUser(id);
+    try {
+        await api.track(id);
+    } catch (error) {
+        report(error);
+    }
+    return user;
+}
+const save = async (data) => await api.post(data);
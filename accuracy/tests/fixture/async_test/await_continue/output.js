@@ -0,0 +1,11 @@
+const { accAwait } = require("swc-plugin-accuracy/lib/calc.js");
+async function load(id) {
+    const user = await accAwait(api.getUser(id), "api.getUser(id)", "input.js:2:24");
+    try {
+        await api.track(id);
+    } catch (error) {
+        report(error);
+    }
+    return user;
+}
+const save = async (data) => await accAwait(api.post(data), "api.post(data)", "input.js:10:36");
@@ -0,0 +1,9 @@
+async function load(id) {
+    'use strict';
+    const user = await api.getUser(id);
+    render(user);
+}
+const save = async (data) => await api.post(data);
+async function noop() {
+    return 1;
+}
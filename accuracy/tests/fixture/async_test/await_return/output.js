@@ -0,0 +1,20 @@
+const { accAwait } = require("swc-plugin-accuracy/lib/calc.js");
+async function load(id) {
+    'use strict';
+    try {
+        const user = await accAwait(api.getUser(id), "api.getUser(id)", "input.js:3:24", true);
+        render(user);
+    } catch (error) {
+        if (error !== accAwait.stop) throw error;
+    }
+}
+const save = async (data) => {
+    try {
+        return await accAwait(api.post(data), "api.post(data)", "input.js:6:36", true);
+    } catch (error) {
+        if (error !== accAwait.stop) throw error;
+    }
+};
+async function noop() {
+    return 1;
+}
@@ -0,0 +1,13 @@
+import { init } from './app';
+const { render } = await import('./renderer');
+export const config = await fetch('/config.json'), retries = 3;
+const timeout = 1000, mod = await import('./mod'), name = mod.name;
+let [first] = await loadUsers();
+var cache = await openCache();
+await init(config);
+function start() {
+    render(first);
+}
+if (retries) {
+    start();
+}
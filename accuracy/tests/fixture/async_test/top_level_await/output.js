@@ -0,0 +1,45 @@
+import { init } from './app';
+let render;
+try {
+    ({ render } = await import('./renderer'));
+} catch (error) {
+    console.error(this, error);
+}
+export let config;
+try {
+    config = await fetch('/config.json');
+} catch (error) {
+    console.error(this, error);
+}
+export const retries = 3;
+const timeout = 1000;
+let mod;
+try {
+    mod = await import('./mod');
+} catch (error) {
+    console.error(this, error);
+}
+const name = mod.name;
+let first;
+try {
+    [first] = await loadUsers();
+} catch (error) {
+    console.error(this, error);
+}
+var cache;
+try {
+    cache = await openCache();
+} catch (error) {
+    console.error(this, error);
+}
+try {
+    await init(config);
+} catch (error) {
+    console.error(this, error);
+}
+function start() {
+    render(first);
+}
+if (retries) {
+    start();
+}
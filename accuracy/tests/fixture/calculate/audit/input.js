@@ -0,0 +1,5 @@
+const total = price * count + fee;
+let sum = 0;
+sum += 0.1;
+sum -= discount / 2;
+if (total === sum) {}
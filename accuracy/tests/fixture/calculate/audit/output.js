@@ -0,0 +1,6 @@
+const { accAuditMul, accAuditAdd, accAuditSub, accAuditDiv, accCong } = require("swc-plugin-accuracy/lib/calc.js");
+const total = accAuditAdd(accAuditMul(price, count, "input.js:1:15"), fee, "input.js:1:15");
+let sum = 0;
+sum = accAuditAdd(sum, 0.1, "input.js:3:1");
+sum = accAuditSub(sum, accAuditDiv(discount, 2, "input.js:4:8"), "input.js:4:1");
+if (accCong(total, sum)) {}
@@ -0,0 +1,3 @@
+const total = price + fee;
+if (total === limit) {}
+const id = 12345678901234567890;
@@ -0,0 +1,4 @@
+const { accAuditAdd, accCong } = require("swc-plugin-accuracy/lib/calc.js");
+const total = accAuditAdd(price, fee, "input.js:1:15");
+if (accCong(total, limit)) {}
+const id = 12345678901234567890;
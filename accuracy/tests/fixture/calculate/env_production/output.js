@@ -0,0 +1,4 @@
+const { accAdd } = /*#__PURE__*/ require("swc-plugin-accuracy/lib/calc.js");
+const total = /*#__PURE__*/ accAdd(price, fee);
+if (total === limit) {}
+const id = "12345678901234567890";
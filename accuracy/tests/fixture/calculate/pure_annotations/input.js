@@ -0,0 +1,6 @@
+const total = price * count + fee;
+let sum = 0;
+sum += 0.1;
+if (total === sum) {}
+const date = new Date(row.createdAt);
+const data = JSON.parse(text);
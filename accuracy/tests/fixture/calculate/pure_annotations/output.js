@@ -0,0 +1,7 @@
+const { accMul, accAdd, accCong, accDate, accJsonParse } = /*#__PURE__*/ require("swc-plugin-accuracy/lib/calc.js");
+const total = /*#__PURE__*/ accAdd(/*#__PURE__*/ accMul(price, count), fee);
+let sum = 0;
+sum = /*#__PURE__*/ accAdd(sum, 0.1);
+if (/*#__PURE__*/ accCong(total, sum)) {}
+const date = /*#__PURE__*/ accDate(row.createdAt);
+const data = accJsonParse(text);
@@ -0,0 +1,8 @@
+const total = price * count + fee;
+let sum = 0;
+sum += 0.1;
+try {
+    JSON.parse(text);
+} catch (error) {
+    invalid = true;
+}
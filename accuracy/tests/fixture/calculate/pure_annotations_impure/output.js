@@ -0,0 +1,9 @@
+const { accAuditMul, accAuditAdd, accJsonParse } = /*#__PURE__*/ require("swc-plugin-accuracy/lib/calc.js");
+const total = accAuditAdd(accAuditMul(price, count, "input.js:1:15"), fee, "input.js:1:15");
+let sum = 0;
+sum = accAuditAdd(sum, 0.1, "input.js:3:1");
+try {
+    accJsonParse(text);
+} catch (error) {
+    invalid = true;
+}
@@ -0,0 +1,12 @@
+const price = 0.1;
+if (price === NaN) {}
+if (Number.NaN !== price) {}
+if (0.1 + 0.2 === 0.3) {}
+if (price * 1.1 == 2) {}
+if (price === 0.5) {}
+const a = price / 0;
+const b = price % 0;
+let c = 1;
+c /= 0;
+const d = parseInt("08");
+const e = Number.parseInt("10", 10);
@@ -0,0 +1,13 @@
+const { accCong, accAdd, accMul, accDiv } = require("swc-plugin-accuracy/lib/calc.js");
+const price = 0.1;
+if (accCong(price, NaN)) {}
+if (Number.NaN !== price) {}
+if (accCong(accAdd(0.1, 0.2), 0.3)) {}
+if (accMul(price, 1.1) == 2) {}
+if (accCong(price, 0.5)) {}
+const a = accDiv(price, 0);
+const b = price % 0;
+let c = 1;
+c = accDiv(c, 0);
+const d = parseInt("08");
+const e = Number.parseInt("10", 10);
@@ -1 +1 @@
-new Date("1982-12-2".replace(/-/g, "/"))
\ No newline at end of file
+new Date("1982/12/2")
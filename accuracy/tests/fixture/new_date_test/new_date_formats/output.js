@@ -0,0 +1,9 @@
+new Date("2020/01/01 10:00");
+new Date("2020/1/1 9:05:30");
+new Date("2020-01-01T10:00:00+08:00");
+new Date('2020-01-01');
+new Date('2020-01-01T10:00:00Z');
+new Date('2020-01-01T10:00:00+08:00');
+new Date('2020-01-01T10:00:00.123Z');
+new Date('2020/01/01 10:00');
+new Date('Jan 1, 2020');
@@ -0,0 +1,3 @@
+new Date('2020-01-01');
+new Date('2020-01-01T00:00:00Z');
+Date.parse(str);
@@ -0,0 +1,4 @@
+const { accDateParse } = require("swc-plugin-accuracy/lib/calc.js");
+new Date("2020/01/01");
+new Date('2020-01-01T00:00:00Z');
+accDateParse(str, "local");
@@ -0,0 +1,9 @@
+new Date(row.createdAt);
+new Date(`${year}-${month}-${day}`);
+new Date(date + ' ' + time);
+Date.parse(str);
+Date.parse('2020-01-01 10:00');
+new Date(1577836800000);
+new Date(ts * 1000);
+new Date(2020, 0, 1);
+new Date();
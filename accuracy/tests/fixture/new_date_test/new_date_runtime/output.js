@@ -0,0 +1,10 @@
+const { accDate, accAdd, accDateParse, accMul } = require("swc-plugin-accuracy/lib/calc.js");
+accDate(row.createdAt);
+accDate(`${year}-${month}-${day}`);
+accDate(accAdd(accAdd(date, ' '), time));
+accDateParse(str);
+Date.parse("2020/01/01 10:00");
+new Date(1577836800000);
+new Date(accMul(ts, 1000));
+new Date(2020, 0, 1);
+new Date();
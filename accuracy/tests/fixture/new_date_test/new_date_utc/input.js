@@ -0,0 +1,4 @@
+new Date('2020-01-01');
+new Date('1982-12-2');
+new Date('2020-01-01 10:00');
+new Date(row.createdAt);
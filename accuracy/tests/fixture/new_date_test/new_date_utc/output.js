@@ -0,0 +1,5 @@
+const { accDate } = require("swc-plugin-accuracy/lib/calc.js");
+new Date("2020-01-01T00:00:00Z");
+new Date("1982-12-02T00:00:00Z");
+new Date("2020/01/01 10:00");
+accDate(row.createdAt, "utc");
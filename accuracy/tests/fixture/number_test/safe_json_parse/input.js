@@ -0,0 +1,3 @@
+const data = JSON.parse(res.body);
+const list = JSON.parse(text, (key, value) => value);
+const raw = JSON.stringify(data);
@@ -0,0 +1,4 @@
+const { accJsonParse } = require("swc-plugin-accuracy/lib/calc.js");
+const data = accJsonParse(res.body);
+const list = accJsonParse(text, (key, value)=>value);
+const raw = JSON.stringify(data);
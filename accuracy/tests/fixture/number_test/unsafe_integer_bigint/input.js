@@ -0,0 +1,6 @@
+const id = 12345678901234567890;
+const max = 9007199254740991;
+const hex = 0x20000000000001;
+const big = 1e21;
+const neg = -9007199254740993;
+const price = 0.1 + 0.2;
@@ -0,0 +1,7 @@
+const { accAdd } = require("swc-plugin-accuracy/lib/calc.js");
+const id = "12345678901234567890";
+const max = 9007199254740991;
+const hex = "9007199254740993";
+const big = 1e21;
+const neg = "-9007199254740993";
+const price = accAdd(0.1, 0.2);
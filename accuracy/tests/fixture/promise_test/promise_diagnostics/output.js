@@ -0,0 +1,5 @@
+function load() {
+    fetch('/api').then(parse);
+    save().catch(report);
+    return fetch('/data');
+}
@@ -0,0 +1 @@
+Promise.all([load(1), load(2)]);
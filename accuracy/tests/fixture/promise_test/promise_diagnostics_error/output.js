@@ -0,0 +1,4 @@
+Promise.all([
+    load(1),
+    load(2)
+]);
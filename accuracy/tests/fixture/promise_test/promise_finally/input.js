@@ -0,0 +1,5 @@
+a.then(function (res) {
+    return res.price * 0.1;
+}).finally(() => {
+    done();
+});
@@ -0,0 +1,8 @@
+const { accMul } = require("swc-plugin-accuracy/lib/calc.js");
+a.then(function (res) {
+    return accMul(res.price, 0.1);
+}).finally(() => {
+    done();
+}).catch((err) => {
+    console.error(err);
+});
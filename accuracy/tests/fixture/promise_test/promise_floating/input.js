@@ -0,0 +1,22 @@
+async function save(data) {
+    return api.post(data);
+}
+const sync = async () => {
+    await api.sync();
+};
+function load() {
+    fetch('/api');
+    Promise.all([save(1), save(2)]);
+    new Promise((resolve) => setTimeout(resolve, 100));
+    save(3).finally(done);
+    sync();
+    const res = fetch('/api').then(parse);
+    notify(fetch('/log'));
+    return a.then(parse);
+}
+async function run() {
+    await a.then(parse);
+    void fetch('/ping');
+    Promise.race([save(4)]).then(onOk, onErr);
+    list.forEach((item) => save(item));
+}
@@ -0,0 +1,44 @@
+async function save(data) {
+    try {
+        return api.post(data);
+    } catch (error) {
+        console.error(this, error);
+    }
+}
+const sync = async () => {
+    try {
+        await api.sync();
+    } catch (error) {
+        console.error(this, error);
+    }
+};
+function load() {
+    fetch('/api').catch((err) => {
+        console.error(err);
+    });
+    Promise.all([save(1), save(2)]).catch((err) => {
+        console.error(err);
+    });
+    new Promise((resolve) => setTimeout(resolve, 100)).catch((err) => {
+        console.error(err);
+    });
+    save(3).finally(done).catch((err) => {
+        console.error(err);
+    });
+    sync().catch((err) => {
+        console.error(err);
+    });
+    const res = fetch('/api').then(parse);
+    notify(fetch('/log'));
+    return a.then(parse);
+}
+async function run() {
+    try {
+        await a.then(parse);
+        void fetch('/ping');
+        Promise.race([save(4)]).then(onOk, onErr);
+        list.forEach((item) => save(item));
+    } catch (error) {
+        console.error(this, error);
+    }
+}
@@ -0,0 +1,3 @@
+a.then(onOk, onErr);
+a.catch(onErr).finally(done);
+a.catch(onErr).then(onOk);
@@ -0,0 +1,5 @@
+a.then(onOk, onErr);
+a.catch(onErr).finally(done);
+a.catch(onErr).then(onOk).catch((err) => {
+    console.error(err);
+});
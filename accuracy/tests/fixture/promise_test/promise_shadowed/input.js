@@ -0,0 +1,15 @@
+async function save(data) {
+    return api.post(data);
+}
+function retry(save) {
+    save();
+}
+function local() {
+    const fetch = (url) => cache.get(url);
+    fetch('/api');
+    save(1);
+}
+function wrap(Promise) {
+    new Promise(run);
+    Promise.all([task]);
+}
@@ -0,0 +1,23 @@
+async function save(data) {
+    try {
+        return api.post(data);
+    } catch (error) {
+        console.error(this, error);
+    }
+}
+function retry(save) {
+    save();
+}
+function local() {
+    const fetch = (url)=>cache.get(url);
+    fetch('/api');
+    save(1).catch((err)=>{
+        console.error(err);
+    });
+}
+function wrap(Promise) {
+    new Promise(run);
+    Promise.all([
+        task
+    ]);
+}
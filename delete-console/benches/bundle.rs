@@ -0,0 +1,101 @@
+//! Times the transform on a large generated bundle.
+//!
+//! `cargo bench` runs the full measurement. Under `cargo test --all-targets`
+//! the bench is only smoke tested on a small bundle, so it doesn't slow the
+//! normal test run.
+
+use std::time::{Duration, Instant};
+
+use delete_console::{Config, ConfigFile, TransformVisitor};
+use swc_core::{
+    common::{sync::Lrc, FileName, Globals, Mark, SourceMap, GLOBALS},
+    ecma::{
+        ast::Program,
+        parser::{parse_file_as_program, Syntax},
+        transforms::base::resolver,
+        visit::{as_folder, FoldWith},
+    },
+};
+
+/// A bundle of `modules` modules mixing plain code and console calls.
+fn bundle(modules: usize) -> String {
+    let mut code = String::new();
+    for i in 0..modules {
+        code.push_str(&format!(
+            r#"
+function module{i}(exports, require) {{
+    const {{ log }} = console;
+    const dep = require("dep{i}");
+    let total = 0;
+    for (let j = 0; j < dep.items.length; j++) {{
+        total += dep.items[j].value * {i};
+        if (total > 100) console.log("total", total);
+    }}
+    log("loaded", {i});
+    dep.ready && console.info("ready");
+    exports.value = total ? (console.debug(total), total) : dep.fallback();
+    exports.handler = () => console.warn("handled");
+    console.error("unexpected", dep.error);
+}}
+"#
+        ));
+    }
+    code
+}
+
+fn parse(code: String) -> Program {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(FileName::Anon, code);
+    parse_file_as_program(
+        &fm,
+        Syntax::Es(Default::default()),
+        Default::default(),
+        None,
+        &mut vec![],
+    )
+    .unwrap()
+}
+
+/// Mean time of one transform of `program` as `file_name`.
+fn run(program: &Program, file_name: &str, iterations: u32) -> Duration {
+    let config = Config::new(
+        vec![],
+        vec!["error".into()],
+        ConfigFile::new(vec!["src/**".into()], vec![]),
+    );
+    GLOBALS.set(&Globals::new(), || {
+        let unresolved_mark = Mark::new();
+        let program = program
+            .clone()
+            .fold_with(&mut resolver(unresolved_mark, Mark::new(), false));
+        let mut total = Duration::ZERO;
+        for _ in 0..iterations {
+            let program = program.clone();
+            let start = Instant::now();
+            let program = program.fold_with(&mut as_folder(TransformVisitor::new(
+                config.clone(),
+                Some(file_name.to_string()),
+                unresolved_mark,
+            )));
+            total += start.elapsed();
+            drop(program);
+        }
+        total / iterations
+    })
+}
+
+fn main() {
+    // `cargo bench` passes `--bench`, `cargo test` does not.
+    let bench = std::env::args().any(|arg| arg == "--bench");
+    let (modules, iterations) = if bench { (5000, 20) } else { (10, 1) };
+    let program = parse(bundle(modules));
+    for (name, file_name) in [
+        ("bundle_matched", "src/bundle.js"),
+        ("bundle_skipped", "vendor/bundle.js"),
+    ] {
+        let time = run(&program, file_name, iterations);
+        if bench {
+            println!("{:<16} {:>12?}/iter", name, time);
+        }
+    }
+}
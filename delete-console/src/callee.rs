@@ -0,0 +1,121 @@
+use std::collections::HashSet;
+
+use swc_core::ecma::{ast::*, utils::find_pat_ids};
+
+use crate::{
+    config::Config,
+    console::{call_callee, prop_name, ConsoleMatcher},
+    method::method_match,
+};
+
+/// The pattern removing `debugger` statements.
+pub const DEBUGGER: &str = "debugger";
+
+/// Checks that a callee pattern has no empty segment, e.g. `logger..debug`.
+pub fn validate_callee(pattern: &str) -> Result<(), String> {
+    if pattern.split('.').any(str::is_empty) {
+        return Err(format!("invalid callee \"{}\"", pattern));
+    }
+    Ok(())
+}
+
+/// The bindings declared at the top level of a file: imports, `require`d
+/// modules and other module-level functions, classes and variables.
+#[derive(Default)]
+pub struct ModuleBindings(HashSet<Id>);
+
+impl ModuleBindings {
+    pub fn collect(program: &Program) -> Self {
+        let mut bindings = ModuleBindings::default();
+        match program {
+            Program::Module(module) => {
+                for item in &module.body {
+                    match item {
+                        ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                            for specifier in &import.specifiers {
+                                let local = match specifier {
+                                    ImportSpecifier::Named(named) => &named.local,
+                                    ImportSpecifier::Default(default) => &default.local,
+                                    ImportSpecifier::Namespace(namespace) => &namespace.local,
+                                };
+                                bindings.0.insert(local.to_id());
+                            }
+                        }
+                        ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => {
+                            bindings.add_decl(&export.decl)
+                        }
+                        ModuleItem::Stmt(Stmt::Decl(decl)) => bindings.add_decl(decl),
+                        _ => {}
+                    }
+                }
+            }
+            Program::Script(script) => {
+                for stmt in &script.body {
+                    if let Stmt::Decl(decl) = stmt {
+                        bindings.add_decl(decl);
+                    }
+                }
+            }
+        }
+        bindings
+    }
+
+    fn add_decl(&mut self, decl: &Decl) {
+        match decl {
+            Decl::Fn(FnDecl { ident, .. }) | Decl::Class(ClassDecl { ident, .. }) => {
+                self.0.insert(ident.to_id());
+            }
+            Decl::Var(var) => {
+                for decl in &var.decls {
+                    self.0.extend(find_pat_ids::<_, Id>(&decl.name));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Matches a callee against a dotted pattern such as `logger.debug`,
+/// `assert.*` or `alert`, segment by segment. The root identifier is a global
+/// or a module-level binding such as an imported `assert`; a parameter or a
+/// local `function alert() {}` shadowing it is left alone.
+fn callee_match(
+    pattern: &str,
+    expr: &Expr,
+    matcher: &ConsoleMatcher,
+    bindings: &ModuleBindings,
+) -> bool {
+    match expr {
+        Expr::Ident(ident) => {
+            !pattern.contains('.')
+                && (matcher.is_unresolved(ident) || bindings.0.contains(&ident.to_id()))
+                && method_match(pattern, &ident.sym)
+        }
+        Expr::This(..) => pattern == "this",
+        Expr::Member(MemberExpr { obj, prop, .. }) => match pattern.rsplit_once('.') {
+            Some((obj_pattern, prop_pattern)) => {
+                prop_name(prop).map_or(false, |prop| method_match(prop_pattern, &prop))
+                    && callee_match(obj_pattern, obj, matcher, bindings)
+            }
+            None => false,
+        },
+        Expr::Paren(ParenExpr { expr, .. }) => callee_match(pattern, expr, matcher, bindings),
+        _ => false,
+    }
+}
+
+/// A call of a callee selected by the `callees` patterns of the config.
+pub fn is_callee_call(
+    expr: &Expr,
+    matcher: &ConsoleMatcher,
+    bindings: &ModuleBindings,
+    config: &Config,
+) -> bool {
+    let Some(callee) = call_callee(expr) else {
+        return false;
+    };
+    config
+        .callees()
+        .iter()
+        .any(|pattern| &**pattern != DEBUGGER && callee_match(pattern, callee, matcher, bindings))
+}
@@ -1,8 +1,18 @@
+use std::{collections::HashMap, fmt::Display};
+
 use serde::{Deserialize, Serialize};
-use serde_json;
-use std::fmt::Display;
 use swc_core::ecma::atoms::JsWord;
 
+use crate::{
+    callee::{validate_callee, DEBUGGER},
+    file::{file_check, rules_match, validate_rule},
+    method::{is_pattern, method_match, Level},
+};
+
+/// Which files consoles are removed from. Rules are globs matched against
+/// the file path relative to the project root, a `!rule` removes files
+/// matched by the other rules of the same list. `excludes` takes precedence
+/// over `includes`, and without plain `includes` rules every file is included.
 #[derive(Clone, Default, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct ConfigFile {
@@ -16,8 +26,52 @@ impl ConfigFile {
     pub fn new(includes: Vec<JsWord>, excludes: Vec<JsWord>) -> ConfigFile {
         ConfigFile { includes, excludes }
     }
+
+    /// No rules at all, consoles are removed from every file.
+    pub fn is_empty(&self) -> bool {
+        self.includes.is_empty() && self.excludes.is_empty()
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        let included = if self.includes.iter().all(|rule| rule.starts_with('!')) {
+            !self
+                .includes
+                .iter()
+                .any(|rule| file_check(&rule[1..], path))
+        } else {
+            rules_match(&self.includes, path)
+        };
+        included && !rules_match(&self.excludes, path)
+    }
+}
+
+/// Overrides which methods and callees are removed in the files matching
+/// `files`. Fields that are set replace the top level ones, the last matching
+/// override wins. In `env` the `files` may be left out to apply to every file.
+#[derive(Clone, Default, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ConfigOverride {
+    #[serde(default)]
+    pub files: Vec<JsWord>,
+    #[serde(default)]
+    pub includes: Option<Vec<JsWord>>,
+    #[serde(default)]
+    pub excludes: Option<Vec<JsWord>>,
+    #[serde(default)]
+    pub level: Option<Level>,
+    #[serde(default)]
+    pub callees: Option<Vec<JsWord>>,
 }
 
+/// Which console methods are removed, in order of precedence:
+///
+/// 1. `excludes`: methods matching any of these are always kept.
+/// 2. `includes`: methods matching any of these are removed.
+/// 3. `level`: methods below this level are removed, e.g. `"warn"` removes
+///    `debug`, `log` and `info` but keeps `warn` and `error`.
+///
+/// When neither `includes` nor `level` is set every method not excluded is
+/// removed. Entries may use `*` and `?` wildcards, e.g. `"time*"`.
 #[derive(Clone, Default, Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct Config {
@@ -25,7 +79,26 @@ pub struct Config {
     includes: Vec<JsWord>,
     #[serde(default)]
     excludes: Vec<JsWord>,
+    #[serde(default)]
+    level: Option<Level>,
+    #[serde(default)]
     file: ConfigFile,
+    #[serde(default)]
+    overrides: Vec<ConfigOverride>,
+    /// Other calls to remove, e.g. `logger.debug`, `assert.*` or `alert`, and
+    /// `debugger` for debugger statements.
+    #[serde(default)]
+    callees: Vec<JsWord>,
+    /// Rules per build environment, e.g. `production`. When set, files are
+    /// left untouched in environments without a rule.
+    #[serde(default)]
+    env: HashMap<String, ConfigOverride>,
+    /// The environment used when the build host doesn't pass one.
+    #[serde(default)]
+    default_env: Option<String>,
+    /// Keep the impure arguments of removed calls, e.g. `counter++`.
+    #[serde(default)]
+    keep_side_effects: bool,
 }
 
 impl Config {
@@ -34,8 +107,40 @@ impl Config {
             includes,
             excludes,
             file,
+            level: None,
+            overrides: vec![],
+            callees: vec![],
+            env: HashMap::new(),
+            default_env: None,
+            keep_side_effects: false,
         }
     }
+
+    pub fn with_level(mut self, level: Level) -> Config {
+        self.level = Some(level);
+        self
+    }
+
+    pub fn with_callees(mut self, callees: Vec<JsWord>) -> Config {
+        self.callees = callees;
+        self
+    }
+
+    pub fn with_env(mut self, env: &str, rule: ConfigOverride) -> Config {
+        self.env.insert(env.to_string(), rule);
+        self
+    }
+
+    pub fn with_default_env(mut self, env: &str) -> Config {
+        self.default_env = Some(env.to_string());
+        self
+    }
+
+    pub fn with_override(mut self, rule: ConfigOverride) -> Config {
+        self.overrides.push(rule);
+        self
+    }
+
     pub fn includes(&self) -> &[JsWord] {
         &self.includes
     }
@@ -47,6 +152,145 @@ impl Config {
     pub fn file(&self) -> &ConfigFile {
         &self.file
     }
+
+    pub fn keep_side_effects(&self) -> bool {
+        self.keep_side_effects
+    }
+
+    pub fn level(&self) -> Option<Level> {
+        self.level
+    }
+
+    pub fn overrides(&self) -> &[ConfigOverride] {
+        &self.overrides
+    }
+
+    pub fn callees(&self) -> &[JsWord] {
+        &self.callees
+    }
+
+    pub fn removes_debugger(&self) -> bool {
+        self.callees.iter().any(|callee| &**callee == DEBUGGER)
+    }
+
+    /// The config for the build environment `env`, falling back to
+    /// `defaultEnv` when the host passes none. The environment rule is
+    /// applied after the overrides, so it takes precedence over them. `None`
+    /// if consoles are not removed in this environment.
+    ///
+    /// Panics when there are environment rules but no environment to pick
+    /// one, rather than silently leaving every file untouched.
+    pub fn for_env(&self, env: Option<&str>) -> Option<Config> {
+        let mut config = self.clone();
+        config.env.clear();
+        if self.env.is_empty() {
+            return Some(config);
+        }
+        let Some(env) = env.or(self.default_env.as_deref()) else {
+            panic!("Invalid plugin config: the build environment is unknown, set defaultEnv");
+        };
+        let mut rule = self.env.get(env)?.clone();
+        if rule.files.is_empty() {
+            // Applied right away as well, so the rule also holds when the
+            // host passes no file name and `for_file` is never called.
+            config.apply(&rule);
+            rule.files.push("**".into());
+        }
+        config.overrides.push(rule);
+        Some(config)
+    }
+
+    /// The config for the file at `path` with the matching overrides applied.
+    pub fn for_file(&self, path: &str) -> Config {
+        let mut config = self.clone();
+        for rule in self.overrides.iter() {
+            if rules_match(&rule.files, path) {
+                config.apply(rule);
+            }
+        }
+        config.overrides.clear();
+        config
+    }
+
+    fn apply(&mut self, rule: &ConfigOverride) {
+        if let Some(includes) = &rule.includes {
+            self.includes = includes.clone();
+        }
+        if let Some(excludes) = &rule.excludes {
+            self.excludes = excludes.clone();
+        }
+        if rule.level.is_some() {
+            self.level = rule.level;
+        }
+        if let Some(callees) = &rule.callees {
+            self.callees = callees.clone();
+        }
+    }
+
+    /// Whether calls of `method` are removed, see [Config] for the precedence.
+    pub fn removes_method(&self, method: &str) -> bool {
+        if self
+            .excludes
+            .iter()
+            .any(|pattern| method_match(pattern, method))
+        {
+            return false;
+        }
+        if self.includes.is_empty() && self.level.is_none() {
+            return true;
+        }
+        self.includes
+            .iter()
+            .any(|pattern| method_match(pattern, method))
+            || self.level.map_or(false, |level| Level::of(method) < level)
+    }
+
+    /// Rejects invalid file rules, and includes that can never take effect
+    /// because the same entry, or an exclude pattern matching it, is also in
+    /// `excludes`. Excluding a method from an include pattern (`includes:
+    /// ["*"], excludes: ["warn"]`) is allowed. Overrides and environment
+    /// rules are checked combined with the top level methods they inherit.
+    pub fn validate(&self) -> Result<(), String> {
+        for rule in self.file.includes.iter().chain(&self.file.excludes) {
+            validate_rule(rule)?;
+        }
+        self.validate_methods()?;
+        for callee in &self.callees {
+            validate_callee(callee)?;
+        }
+        for rule in &self.overrides {
+            if rule.files.is_empty() {
+                return Err("override without files".to_string());
+            }
+        }
+        for rule in self.overrides.iter().chain(self.env.values()) {
+            for file in &rule.files {
+                validate_rule(file)?;
+            }
+            let mut config = self.clone();
+            config.apply(rule);
+            config.validate_methods()?;
+            for callee in rule.callees.iter().flatten() {
+                validate_callee(callee)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_methods(&self) -> Result<(), String> {
+        for include in &self.includes {
+            let overlap = self.excludes.iter().find(|exclude| {
+                *exclude == include || (!is_pattern(include) && method_match(exclude, include))
+            });
+            if let Some(exclude) = overlap {
+                return Err(format!(
+                    "\"{}\" in includes overlaps \"{}\" in excludes",
+                    include, exclude
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Display for Config {
@@ -61,5 +305,9 @@ impl Display for Config {
 }
 
 pub fn parse_config(config_str: &str) -> Config {
-    serde_json::from_str::<Config>(config_str).expect("Invalid plugin config")
+    let config = serde_json::from_str::<Config>(config_str).expect("Invalid plugin config");
+    if let Err(err) = config.validate() {
+        panic!("Invalid plugin config: {}", err);
+    }
+    config
 }
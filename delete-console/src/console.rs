@@ -0,0 +1,396 @@
+use std::collections::{HashMap, HashSet};
+
+use swc_core::{
+    common::{Mark, SyntaxContext, DUMMY_SP},
+    ecma::{
+        ast::*,
+        atoms::JsWord,
+        visit::{Visit, VisitWith},
+    },
+};
+
+use crate::config::Config;
+
+/// Global objects `console` can be read from, e.g. `window.console.log`.
+const GLOBAL_OBJECTS: [&str; 4] = ["window", "globalThis", "self", "global"];
+
+/// Finds console methods through the global `console` and its aliases.
+///
+/// Identifiers are compared by their syntax context, so a local binding named
+/// `console` (a parameter, an import, ...) is never mistaken for the global
+/// one. This relies on the resolver swc runs before plugins.
+pub struct ConsoleMatcher {
+    unresolved_ctxt: SyntaxContext,
+    /// `const c = console`
+    consoles: HashSet<Id>,
+    /// `const { log } = console`, `const log = console.log`
+    methods: HashMap<Id, JsWord>,
+}
+
+impl ConsoleMatcher {
+    pub fn new(unresolved_mark: Mark) -> Self {
+        ConsoleMatcher {
+            unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
+            consoles: HashSet::new(),
+            methods: HashMap::new(),
+        }
+    }
+
+    /// An identifier not bound in the file, i.e. a global.
+    pub fn is_unresolved(&self, ident: &Ident) -> bool {
+        ident.span.ctxt == self.unresolved_ctxt
+    }
+
+    fn is_global(&self, ident: &Ident, names: &[&str]) -> bool {
+        self.is_unresolved(ident) && names.contains(&&*ident.sym)
+    }
+
+    /// `console`, an alias of it, or `window.console` and the like.
+    fn is_console(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Ident(ident) => {
+                self.is_global(ident, &["console"]) || self.consoles.contains(&ident.to_id())
+            }
+            Expr::Member(MemberExpr { obj, prop, .. }) => {
+                matches!(&**obj, Expr::Ident(obj) if self.is_global(obj, &GLOBAL_OBJECTS))
+                    && prop_name(prop).map_or(false, |prop| &*prop == "console")
+            }
+            Expr::Paren(ParenExpr { expr, .. }) => self.is_console(expr),
+            _ => false,
+        }
+    }
+
+    /// `console.log`, `console['log']` or an alias of a console method.
+    fn method(&self, expr: &Expr) -> Option<JsWord> {
+        match expr {
+            Expr::Ident(ident) => self.methods.get(&ident.to_id()).cloned(),
+            Expr::Member(MemberExpr { obj, prop, .. }) if self.is_console(obj) => prop_name(prop),
+            // console?.log
+            Expr::OptChain(OptChainExpr { base, .. }) => match &**base {
+                OptChainBase::Member(MemberExpr { obj, prop, .. }) if self.is_console(obj) => {
+                    prop_name(prop)
+                }
+                _ => None,
+            },
+            Expr::Paren(ParenExpr { expr, .. }) => self.method(expr),
+            _ => None,
+        }
+    }
+
+    /// `method.call(...)`, `method.apply(...)` or `method.bind(...)` on a
+    /// console method.
+    fn function_method(&self, expr: &Expr, names: &[&str]) -> Option<JsWord> {
+        if let Expr::Member(MemberExpr { obj, prop, .. }) = expr {
+            if prop_name(prop).map_or(false, |prop| names.contains(&&*prop)) {
+                return self.method(obj);
+            }
+        }
+        None
+    }
+
+    /// The console method called by `expr`, including `console.log.call(...)`
+    /// and the optional call `console.log?.(...)`.
+    pub fn call_method(&self, expr: &Expr) -> Option<JsWord> {
+        let callee = call_callee(expr)?;
+        self.method(callee)
+            .or_else(|| self.function_method(callee, &["call", "apply"]))
+    }
+
+    /// The console method `expr` evaluates to when used as a value, e.g.
+    /// `.then(console.log)` or `console.log.bind(console)`.
+    pub fn reference_method(&self, expr: &Expr) -> Option<JsWord> {
+        match call_callee(expr) {
+            Some(callee) => self.function_method(callee, &["bind"]),
+            None => self.method(expr),
+        }
+    }
+
+    fn collect_destructured(&mut self, prop: &ObjectPatProp) {
+        match prop {
+            // const { log } = console
+            ObjectPatProp::Assign(AssignPatProp {
+                key, value: None, ..
+            }) => {
+                self.methods.insert(key.to_id(), key.sym.clone());
+            }
+            // const { log: print } = console
+            ObjectPatProp::KeyValue(KeyValuePatProp { key, value }) => {
+                let method = match key {
+                    PropName::Ident(ident) => ident.sym.clone(),
+                    PropName::Str(str) => str.value.clone(),
+                    _ => return,
+                };
+                if let Pat::Ident(binding) = &**value {
+                    self.methods.insert(binding.id.to_id(), method);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Visit for ConsoleMatcher {
+    // Only `const` bindings are followed, others may be reassigned later.
+    fn visit_var_decl(&mut self, n: &VarDecl) {
+        if n.kind == VarDeclKind::Const {
+            for decl in &n.decls {
+                let Some(init) = &decl.init else {
+                    continue;
+                };
+                if self.is_console(init) {
+                    match &decl.name {
+                        Pat::Ident(binding) => {
+                            self.consoles.insert(binding.id.to_id());
+                        }
+                        Pat::Object(object) => {
+                            for prop in &object.props {
+                                self.collect_destructured(prop);
+                            }
+                        }
+                        _ => {}
+                    }
+                } else if let Some(method) = self.reference_method(init) {
+                    if let Pat::Ident(binding) = &decl.name {
+                        self.methods.insert(binding.id.to_id(), method);
+                    }
+                }
+            }
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// The callee of a call `f(...)` or an optional call `f?.(...)`.
+pub fn call_callee(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            ..
+        }) => Some(callee),
+        Expr::OptChain(OptChainExpr { base, .. }) => match &**base {
+            OptChainBase::Call(OptCall { callee, .. }) => Some(callee),
+            OptChainBase::Member(..) => None,
+        },
+        _ => None,
+    }
+}
+
+pub fn prop_name(prop: &MemberProp) -> Option<JsWord> {
+    match prop {
+        MemberProp::Ident(ident) => Some(ident.sym.clone()),
+        MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+            Expr::Lit(Lit::Str(str)) => Some(str.value.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// A call of a console method selected by the config.
+pub fn is_console_call(expr: &Expr, matcher: &ConsoleMatcher, config: &Config) -> bool {
+    matcher
+        .call_method(expr)
+        .map_or(false, |method| config.removes_method(&method))
+}
+
+/// A console method selected by the config used as a value.
+pub fn is_console_reference(expr: &Expr, matcher: &ConsoleMatcher, config: &Config) -> bool {
+    matcher
+        .reference_method(expr)
+        .map_or(false, |method| config.removes_method(&method))
+}
+
+/// `void 0`, the value a removed console call evaluates to.
+pub fn void_zero() -> Expr {
+    Expr::Unary(UnaryExpr {
+        span: DUMMY_SP,
+        op: UnaryOp::Void,
+        arg: Box::new(Expr::Lit(Lit::Num(Number {
+            span: DUMMY_SP,
+            value: 0.0,
+            raw: None,
+        }))),
+    })
+}
+
+pub fn is_void_zero(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Unary(UnaryExpr {
+            op: UnaryOp::Void,
+            arg,
+            ..
+        }) if matches!(&**arg, Expr::Lit(Lit::Num(Number { value, .. })) if *value == 0.0)
+    )
+}
+
+/// Arguments whose evaluation can be dropped: literals, identifiers, simple
+/// member reads and function expressions.
+fn is_pure(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(..) | Expr::Ident(..) | Expr::This(..) | Expr::Fn(..) | Expr::Arrow(..) => true,
+        Expr::Tpl(Tpl { exprs, .. }) => exprs.is_empty(),
+        Expr::Member(MemberExpr { obj, prop, .. }) => {
+            is_pure(obj)
+                && match prop {
+                    MemberProp::Computed(ComputedPropName { expr, .. }) => {
+                        matches!(&**expr, Expr::Lit(..))
+                    }
+                    _ => true,
+                }
+        }
+        Expr::Unary(UnaryExpr {
+            op: UnaryOp::Bang | UnaryOp::TypeOf | UnaryOp::Void,
+            arg,
+            ..
+        }) => is_pure(arg),
+        Expr::Unary(UnaryExpr {
+            op: UnaryOp::Minus | UnaryOp::Plus,
+            arg,
+            ..
+        }) => matches!(&**arg, Expr::Lit(..)),
+        Expr::Paren(ParenExpr { expr, .. }) => is_pure(expr),
+        _ => false,
+    }
+}
+
+/// Takes the impure arguments out of a removed call. A spread argument keeps
+/// its iteration as `[...arg]`, even of an identifier, since iterating may run
+/// user code (iterators, getters).
+pub fn take_side_effects(call: &mut Expr) -> Vec<Expr> {
+    let args = match call {
+        Expr::Call(CallExpr { args, .. }) => args,
+        Expr::OptChain(OptChainExpr { base, .. }) => match &mut **base {
+            OptChainBase::Call(OptCall { args, .. }) => args,
+            OptChainBase::Member(..) => return vec![],
+        },
+        _ => return vec![],
+    };
+    args.drain(..)
+        .filter(|arg| arg.spread.is_some() || !is_pure(&arg.expr))
+        .map(|arg| match arg.spread {
+            Some(_) => Expr::Array(ArrayLit {
+                span: DUMMY_SP,
+                elems: vec![Some(arg)],
+            }),
+            None => *arg.expr,
+        })
+        .collect()
+}
+
+/// `(a, b, void 0)`, evaluating the kept arguments in order.
+pub fn with_void_zero(exprs: Vec<Expr>) -> Expr {
+    if exprs.is_empty() {
+        return void_zero();
+    }
+    Expr::Seq(SeqExpr {
+        span: DUMMY_SP,
+        exprs: exprs
+            .into_iter()
+            .chain([void_zero()])
+            .map(Box::new)
+            .collect(),
+    })
+}
+
+/// `() => {}`, a replacement for console methods passed around as values.
+pub fn noop_fn() -> Expr {
+    Expr::Arrow(ArrowExpr {
+        span: DUMMY_SP,
+        params: vec![],
+        body: Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+            span: DUMMY_SP,
+            stmts: vec![],
+        })),
+        is_async: false,
+        is_generator: false,
+        type_params: None,
+        return_type: None,
+    })
+}
+
+fn logical(op: BinaryOp, left: Box<Expr>, right: Box<Expr>) -> Box<Expr> {
+    Box::new(Expr::Bin(BinExpr {
+        span: DUMMY_SP,
+        op,
+        left,
+        right,
+    }))
+}
+
+/// Simplifies an expression whose result is never used, dropping the `void 0`
+/// left behind by removed console calls. Returns `None` if nothing is left to
+/// evaluate.
+pub fn drop_unused(expr: Box<Expr>) -> Option<Box<Expr>> {
+    if is_void_zero(&expr) {
+        return None;
+    }
+    match *expr {
+        Expr::Paren(ParenExpr { span, expr }) => {
+            drop_unused(expr).map(|expr| Box::new(Expr::Paren(ParenExpr { span, expr })))
+        }
+        Expr::Seq(SeqExpr { span, exprs }) => {
+            let mut exprs: Vec<_> = exprs.into_iter().filter_map(drop_unused).collect();
+            match exprs.len() {
+                0 => None,
+                1 => exprs.pop(),
+                _ => Some(Box::new(Expr::Seq(SeqExpr { span, exprs }))),
+            }
+        }
+        Expr::Bin(BinExpr {
+            span,
+            op: op @ (BinaryOp::LogicalAnd | BinaryOp::LogicalOr | BinaryOp::NullishCoalescing),
+            left,
+            right,
+        }) => {
+            // `void 0 && x` never evaluates `x`, `void 0 || x` always does
+            if is_void_zero(&left) {
+                return match op {
+                    BinaryOp::LogicalAnd => None,
+                    _ => drop_unused(right),
+                };
+            }
+            match drop_unused(right) {
+                Some(right) => Some(Box::new(Expr::Bin(BinExpr {
+                    span,
+                    op,
+                    left,
+                    right,
+                }))),
+                None => drop_unused(left),
+            }
+        }
+        Expr::Cond(CondExpr {
+            span,
+            test,
+            cons,
+            alt,
+        }) => match (drop_unused(cons), drop_unused(alt)) {
+            (None, None) => drop_unused(test),
+            (Some(cons), None) => Some(logical(BinaryOp::LogicalAnd, test, cons)),
+            (None, Some(alt)) => Some(logical(BinaryOp::LogicalOr, test, alt)),
+            (Some(cons), Some(alt)) => Some(Box::new(Expr::Cond(CondExpr {
+                span,
+                test,
+                cons,
+                alt,
+            }))),
+        },
+        expr => Some(Box::new(expr)),
+    }
+}
+
+/// Drops `void 0` from the non-last expressions of a sequence, whose values are
+/// discarded.
+pub fn simplify_seq(seq: &mut SeqExpr) {
+    let Some(last) = seq.exprs.pop() else {
+        return;
+    };
+    let mut exprs: Vec<_> = seq.exprs.drain(..).filter_map(drop_unused).collect();
+    // `(0, obj.fn)()` calls `fn` without `this`, keep a leading expression for it
+    if exprs.is_empty() && matches!(&*last, Expr::Member(..) | Expr::OptChain(..)) {
+        exprs.push(Box::new(void_zero()));
+    }
+    exprs.push(last);
+    seq.exprs = exprs;
+}
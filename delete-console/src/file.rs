@@ -1,52 +1,123 @@
-use std::path::{Component, PathBuf};
+use glob::{MatchOptions, Pattern};
 
-pub fn file_check(rule: String, path: String) -> bool {
-    let binding = PathBuf::from(path.clone());
-    let path_buf: Vec<_> = binding.components().collect();
-    let rule_vec: Vec<&str> = rule.split('/').collect();
-    //文件扩展名
-    let file_extions = if let Some(last) = rule_vec.clone().pop() {
-        if last.contains('.') {
-            let file_extends_vec: Vec<&str> = last.split('.').collect();
-            file_extends_vec.clone().pop()
-        } else {
-            Option::None
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    // `*` and `?` never match `/`, only `**` crosses directories.
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Expands `{a,b}` alternations, including nested ones, into plain patterns.
+fn expand_braces(rule: &str) -> Vec<String> {
+    let Some(open) = rule.find('{') else {
+        return vec![rule.to_string()];
+    };
+    // Find the matching `}` and the top level `,` between the braces.
+    let mut depth = 0;
+    let mut close = None;
+    let mut commas = vec![];
+    for (index, c) in rule[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(open + index);
+                    break;
+                }
+            }
+            ',' if depth == 1 => commas.push(open + index),
+            _ => {}
         }
-    } else {
-        Option::None
+    }
+    // An unclosed brace is matched literally.
+    let Some(close) = close else {
+        return vec![rule.to_string()];
     };
+    let (prefix, suffix) = (&rule[..open], &rule[close + 1..]);
+    let mut starts = vec![open + 1];
+    starts.extend(commas.iter().map(|comma| comma + 1));
+    let mut ends = commas;
+    ends.push(close);
+    starts
+        .into_iter()
+        .zip(ends)
+        .flat_map(|(start, end)| {
+            expand_braces(&format!("{}{}{}", prefix, &rule[start..end], suffix))
+        })
+        .collect()
+}
 
-    let mut path_index = 0;
-    let mut rule_index = 0;
-    let mut res = true;
+/// Checks that every alternative of a rule is a valid glob.
+pub fn validate_rule(rule: &str) -> Result<(), String> {
+    let rule = rule.strip_prefix('!').unwrap_or(rule);
+    for pattern in expand_braces(rule) {
+        Pattern::new(&pattern).map_err(|err| format!("invalid file rule \"{}\": {}", rule, err))?;
+    }
+    Ok(())
+}
 
-    while rule_index < rule_vec.len() - 1 && path_index < path_buf.len() {
-        let rule_parrent = rule_vec[rule_index];
-        let path_dir = path_buf[path_index];
-        if rule_parrent == "**" {
-            let rule_component = Component::Normal(rule_vec[rule_index + 1].as_ref());
-            if rule_component == path_dir {
-                rule_index = rule_index + 2;
-            }
-            path_index = path_index + 1;
-        } else {
-            if rule_vec[rule_index] == path_dir.as_os_str() {
-                rule_index = rule_index + 1;
+/// Normalizes a path for matching: `\` becomes `/`, `.` and `..` segments are
+/// resolved lexically and a Windows drive letter is lowercased.
+pub fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let (root, rest) = match path.as_bytes() {
+        [drive, b':', ..] if drive.is_ascii_alphabetic() => {
+            let rest = path[2..].trim_start_matches('/');
+            (format!("{}:/", (*drive as char).to_ascii_lowercase()), rest)
+        }
+        [b'/', ..] => ("/".to_string(), path.trim_start_matches('/')),
+        _ => (String::new(), &path[..]),
+    };
+    let mut segments: Vec<&str> = vec![];
+    for segment in rest.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." if matches!(segments.last(), Some(last) if *last != "..") => {
+                segments.pop();
             }
-            res = false;
-            break;
+            // `..` above the root of an absolute path stays at the root.
+            ".." if !root.is_empty() => {}
+            _ => segments.push(segment),
         }
     }
-    if rule_index < rule_vec.len() {
-        if let Some(extions) = file_extions {
-            if path_index < path_buf.len() - 1 || !path.ends_with(extions) {
-                res = false;
-            }
-        } else {
-            if rule_vec[rule_vec.len() - 1] != path_buf[path_index].as_os_str() {
-                res = false
-            }
+    format!("{}{}", root, segments.join("/"))
+}
+
+/// The path matched against file rules: relative to `cwd` when the file is
+/// inside it, so rules such as `src/**/*.js` work on every build host.
+pub fn relative_path(path: &str, cwd: Option<&str>) -> String {
+    let path = normalize_path(path);
+    if let Some(cwd) = cwd.map(normalize_path) {
+        let prefix = if cwd.ends_with('/') { cwd } else { cwd + "/" };
+        if let Some(relative) = path.strip_prefix(&prefix) {
+            return relative.to_string();
+        }
+    }
+    path
+}
+
+/// Matches `path` against a glob rule supporting `*`, `?`, `**`, `[abc]`,
+/// `[!abc]` and `{a,b}`. Invalid rules match nothing.
+pub fn file_check(rule: &str, path: &str) -> bool {
+    // Rules are written relative to the project, `./src/**` is `src/**`.
+    let rule = rule.replace('\\', "/");
+    let rule = rule.trim_start_matches("./");
+    expand_braces(rule).iter().any(|pattern| {
+        Pattern::new(pattern).map_or(false, |pattern| pattern.matches_with(path, MATCH_OPTIONS))
+    })
+}
+
+/// Matches `path` against a list of rules: it must match a plain rule and no
+/// negated `!rule`.
+pub fn rules_match<R: AsRef<str>>(rules: &[R], path: &str) -> bool {
+    let mut matched = false;
+    for rule in rules {
+        match rule.as_ref().strip_prefix('!') {
+            Some(negated) if file_check(negated, path) => return false,
+            Some(_) => {}
+            None => matched = matched || file_check(rule.as_ref(), path),
         }
     }
-    res
+    matched
 }
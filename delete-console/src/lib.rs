@@ -1,47 +1,74 @@
 use swc_common::plugin::metadata::TransformPluginMetadataContextKind;
 use swc_core::{
-    common::util::take::Take,
+    common::{util::take::Take, Mark, Spanned},
     ecma::{
         ast::*,
-        visit::{as_folder, FoldWith, VisitMut, VisitMutWith},
+        visit::{as_folder, FoldWith, VisitMut, VisitMutWith, VisitWith},
     },
     plugin::{plugin_transform, proxies::TransformPluginProgramMetadata},
 };
+mod callee;
 mod config;
+mod console;
 mod file;
-pub use config::{parse_config, Config, ConfigFile};
-pub use file::file_check;
+mod method;
+use callee::{is_callee_call, ModuleBindings};
+pub use config::{parse_config, Config, ConfigFile, ConfigOverride};
+use console::{
+    drop_unused, is_console_call, is_console_reference, is_void_zero, noop_fn, simplify_seq,
+    take_side_effects, void_zero, with_void_zero, ConsoleMatcher,
+};
+pub use file::{file_check, normalize_path, relative_path};
+pub use method::Level;
 
 pub struct TransformVisitor {
     pub config: Config,
     pub file_name: Option<String>,
+    matcher: ConsoleMatcher,
+    /// Module-level bindings the `callees` patterns may refer to.
+    bindings: ModuleBindings,
+    /// Set when a console call was removed while visiting the current
+    /// statement, which then needs to be simplified.
+    removed: bool,
+    /// The next expression is an assignment target or the operand of
+    /// `delete`/`typeof`, where a console method reference must stay.
+    target: bool,
 }
 
 impl TransformVisitor {
-    pub fn new(config: Config, file_name: Option<String>) -> Self {
-        TransformVisitor { config, file_name }
+    /// `unresolved_mark` is the mark the resolver applied to global
+    /// identifiers, used to tell the global `console` from local bindings.
+    pub fn new(config: Config, file_name: Option<String>, unresolved_mark: Mark) -> Self {
+        TransformVisitor {
+            config: match &file_name {
+                Some(file_name) => config.for_file(file_name),
+                None => config,
+            },
+            file_name,
+            matcher: ConsoleMatcher::new(unresolved_mark),
+            bindings: ModuleBindings::default(),
+            removed: false,
+            target: false,
+        }
+    }
+
+    fn file_matched(&self) -> bool {
+        match &self.file_name {
+            Some(file_name) => self.config.file().matches(file_name),
+            // Without a file name only a config without file rules applies.
+            None => self.config.file().is_empty(),
+        }
     }
 }
 
-fn stmt_delete_console(n: &mut Stmt, config: Config) {
+/// Removes an expression statement whose value is unused once the console
+/// calls in it are gone.
+fn stmt_delete_console(n: &mut Stmt) {
     if let Stmt::Expr(expr_stmt) = n {
-        if let Expr::Call(call) = &*expr_stmt.expr {
-            if let Callee::Expr(callee_expr) = &call.callee {
-                if let Expr::Member(member) = &**callee_expr {
-                    if let Expr::Ident(indet) = &*member.obj {
-                        print!("Stmt Ident is {}\n", indet);
-                        if indet.sym.eq("console".into()) {
-                            print!("delete console config is {:?}\n", config);
-                            if let MemberProp::Ident(m_ident) = &member.prop {
-                                if config.includes().contains(&m_ident.sym) {
-                                    n.take();
-                                } else if !config.excludes().contains(&m_ident.sym) {
-                                    n.take();
-                                }
-                            }
-                        }
-                    }
-                }
+        match drop_unused(expr_stmt.expr.take()) {
+            Some(expr) => expr_stmt.expr = expr,
+            None => {
+                n.take();
             }
         }
     }
@@ -51,17 +78,100 @@ impl VisitMut for TransformVisitor {
     // Implement necessary visit_mut_* methods for actual custom transform.
     // A comprehensive list of possible visitor methods can be found here:
     // https://rustdoc.swc.rs/swc_ecma_visit/trait.VisitMut.html
+    fn visit_mut_program(&mut self, n: &mut Program) {
+        // The file rules are checked once, other files are left untouched.
+        if !self.file_matched() {
+            return;
+        }
+        // Aliases such as `const { log } = console` and the module-level
+        // bindings are collected up front.
+        n.visit_with(&mut self.matcher);
+        self.bindings = ModuleBindings::collect(n);
+        n.visit_mut_children_with(self);
+    }
+
     fn visit_mut_stmt(&mut self, n: &mut Stmt) {
+        if matches!(n, Stmt::Debugger(..)) && self.config.removes_debugger() {
+            n.take();
+            return;
+        }
+        let removed = std::mem::replace(&mut self.removed, false);
         n.visit_mut_children_with(self);
 
-        for file_include_rule in self.config.file().includes.clone() {
-            if let Some(file_name) = &self.file_name {
-                if file_check(
-                    file_include_rule.as_ref().to_string(),
-                    file_name.to_string(),
-                ) {
-                    stmt_delete_console(n, self.config.clone())
+        if self.removed {
+            stmt_delete_console(n)
+        }
+        self.removed = removed;
+    }
+
+    fn visit_mut_expr(&mut self, n: &mut Expr) {
+        let target = std::mem::take(&mut self.target);
+        // The arguments are dropped together with the call, unless they have
+        // side effects and `keepSideEffects` is set.
+        if is_console_call(n, &self.matcher, &self.config)
+            || is_callee_call(n, &self.matcher, &self.bindings, &self.config)
+        {
+            *n = if self.config.keep_side_effects() {
+                let mut effects = take_side_effects(n);
+                for effect in &mut effects {
+                    effect.visit_mut_with(self);
                 }
+                with_void_zero(effects)
+            } else {
+                void_zero()
+            };
+            self.removed = true;
+            return;
+        }
+        if !target && is_console_reference(n, &self.matcher, &self.config) {
+            *n = noop_fn();
+            self.removed = true;
+            return;
+        }
+        // `(console.log) = f` is still an assignment target
+        self.target = target && matches!(n, Expr::Paren(..));
+        n.visit_mut_children_with(self);
+
+        if let Expr::Seq(seq) = n {
+            simplify_seq(seq);
+            if seq.exprs.len() == 1 {
+                *n = *seq.exprs.remove(0);
+            }
+        }
+    }
+
+    fn visit_mut_pat_or_expr(&mut self, n: &mut PatOrExpr) {
+        self.target = matches!(n, PatOrExpr::Expr(..));
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_pat(&mut self, n: &mut Pat) {
+        // `[console.log] = handlers`, `for (console.log in obj)`
+        self.target = matches!(n, Pat::Expr(..));
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_update_expr(&mut self, n: &mut UpdateExpr) {
+        self.target = true;
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_unary_expr(&mut self, n: &mut UnaryExpr) {
+        // `delete console.log` and `typeof console.log === "function"` guards
+        self.target = matches!(n.op, UnaryOp::Delete | UnaryOp::TypeOf);
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_arrow_expr(&mut self, n: &mut ArrowExpr) {
+        n.visit_mut_children_with(self);
+
+        // `() => console.log(e)` becomes `() => {}`
+        if let BlockStmtOrExpr::Expr(expr) = &*n.body {
+            if is_void_zero(expr) {
+                n.body = Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+                    span: expr.span(),
+                    stmts: vec![],
+                }));
             }
         }
     }
@@ -107,9 +217,21 @@ pub fn process_transform(program: Program, _metadata: TransformPluginProgramMeta
             .get_transform_plugin_config()
             .expect("load plugin config failed"),
     );
-    let file_name = _metadata.get_context(&TransformPluginMetadataContextKind::Filename);
-    println!("file_name is {:?}", file_name);
-    program.fold_with(&mut as_folder(TransformVisitor::new(config, file_name)))
+    // Without a rule for the build environment the file is left untouched.
+    let env = _metadata.get_context(&TransformPluginMetadataContextKind::Env);
+    let Some(config) = config.for_env(env.as_deref()) else {
+        return program;
+    };
+    // File rules are matched against the path relative to the project root.
+    let cwd = _metadata.get_context(&TransformPluginMetadataContextKind::Cwd);
+    let file_name = _metadata
+        .get_context(&TransformPluginMetadataContextKind::Filename)
+        .map(|file_name| relative_path(&file_name, cwd.as_deref()));
+    program.fold_with(&mut as_folder(TransformVisitor::new(
+        config,
+        file_name,
+        _metadata.unresolved_mark,
+    )))
 }
 
 // An example to test plugin transform.
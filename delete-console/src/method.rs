@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+/// Severity of console methods, used by the `level` shortcut.
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum Level {
+    Debug,
+    Log,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    /// Methods not listed here (`table`, `group`, `time`, ...) count as `log`.
+    pub fn of(method: &str) -> Level {
+        match method {
+            "debug" | "trace" => Level::Debug,
+            "info" => Level::Info,
+            "warn" => Level::Warn,
+            "error" | "assert" => Level::Error,
+            _ => Level::Log,
+        }
+    }
+}
+
+pub fn is_pattern(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+/// Matches a method name against a pattern where `*` matches any run of
+/// characters and `?` a single one, e.g. `time*` or `group?`.
+pub fn method_match(pattern: &str, method: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let method: Vec<char> = method.chars().collect();
+    let (mut p, mut m) = (0, 0);
+    // Position after the last `*` and the method position it was tried at.
+    let mut backtrack = None;
+    while m < method.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                backtrack = Some((p, m));
+            }
+            Some(&c) if c == '?' || c == method[m] => {
+                p += 1;
+                m += 1;
+            }
+            _ => match backtrack {
+                Some((bp, bm)) => {
+                    p = bp;
+                    m = bm + 1;
+                    backtrack = Some((bp, bm + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
@@ -0,0 +1,230 @@
+use delete_console::{parse_config, Config, ConfigFile, ConfigOverride, Level, TransformVisitor};
+use swc_core::common::{Globals, Mark, GLOBALS};
+
+fn config(includes: &[&str], excludes: &[&str]) -> Config {
+    Config::new(
+        includes.iter().map(|&method| method.into()).collect(),
+        excludes.iter().map(|&method| method.into()).collect(),
+        ConfigFile::default(),
+    )
+}
+
+#[test]
+fn removes_method() {
+    let cases = [
+        (config(&[], &[]), "log", true),
+        (config(&[], &["warn"]), "warn", false),
+        (config(&["log"], &[]), "log", true),
+        (config(&["log"], &[]), "info", false),
+        (config(&["*"], &["warn", "error"]), "error", false),
+        (config(&["*"], &["warn", "error"]), "table", true),
+        (config(&["time?nd"], &[]), "timeEnd", true),
+        (config(&["time*"], &[]), "timeLog", true),
+        (config(&["time*"], &[]), "table", false),
+        (config(&[], &[]).with_level(Level::Warn), "log", true),
+        (config(&[], &[]).with_level(Level::Warn), "warn", false),
+        (config(&["warn"], &[]).with_level(Level::Info), "warn", true),
+        (
+            config(&[], &["debug"]).with_level(Level::Warn),
+            "debug",
+            false,
+        ),
+    ];
+    for (config, method, expected) in cases {
+        assert_eq!(
+            config.removes_method(method),
+            expected,
+            "{} with {:?}",
+            method,
+            config
+        );
+    }
+}
+
+fn files(rules: &[&str]) -> Vec<swc_core::ecma::atoms::JsWord> {
+    rules.iter().map(|&rule| rule.into()).collect()
+}
+
+#[test]
+fn overrides() {
+    let config = config(&[], &[])
+        .with_level(Level::Warn)
+        .with_override(ConfigOverride {
+            files: files(&["src/legacy/**"]),
+            level: Some(Level::Error),
+            ..Default::default()
+        })
+        .with_override(ConfigOverride {
+            files: files(&["src/**/*.test.js"]),
+            excludes: Some(files(&["*"])),
+            ..Default::default()
+        });
+    let cases = [
+        ("src/index.js", "log", true),
+        ("src/index.js", "warn", false),
+        ("src/legacy/index.js", "warn", true),
+        ("src/legacy/index.js", "error", false),
+        // the last matching override wins
+        ("src/legacy/a.test.js", "log", false),
+        ("src/a.test.js", "log", false),
+    ];
+    for (path, method, expected) in cases {
+        assert_eq!(
+            config.for_file(path).removes_method(method),
+            expected,
+            "{} in {}",
+            method,
+            path
+        );
+    }
+}
+
+#[test]
+fn env_rules() {
+    let with_env = config(&[], &[])
+        .with_env(
+            "production",
+            ConfigOverride {
+                level: Some(Level::Warn),
+                ..Default::default()
+            },
+        )
+        .with_env(
+            "staging",
+            ConfigOverride {
+                files: files(&["src/legacy/**"]),
+                level: Some(Level::Log),
+                ..Default::default()
+            },
+        )
+        .with_override(ConfigOverride {
+            files: files(&["src/legacy/**"]),
+            level: Some(Level::Error),
+            ..Default::default()
+        });
+    // no rule for the environment, files are left untouched
+    assert!(with_env.for_env(Some("development")).is_none());
+    let cases = [
+        ("production", "src/index.js", "log", true),
+        ("production", "src/index.js", "warn", false),
+        // environment rules take precedence over the overrides
+        ("production", "src/legacy/index.js", "warn", false),
+        ("production", "src/legacy/index.js", "log", true),
+        ("staging", "src/legacy/index.js", "debug", true),
+        ("staging", "src/legacy/index.js", "log", false),
+        ("staging", "src/index.js", "log", true),
+    ];
+    for (env, path, method, expected) in cases {
+        assert_eq!(
+            with_env
+                .for_env(Some(env))
+                .unwrap()
+                .for_file(path)
+                .removes_method(method),
+            expected,
+            "{} in {} for {}",
+            method,
+            path,
+            env
+        );
+    }
+    // without env rules every environment uses the top level config
+    assert!(config(&[], &[]).for_env(Some("development")).is_some());
+    assert!(config(&[], &[]).for_env(None).is_some());
+    // defaultEnv is used when the host passes no environment
+    let production = ConfigOverride::default();
+    let default_env = config(&[], &[])
+        .with_env("production", production)
+        .with_default_env("production");
+    assert!(default_env.for_env(None).is_some());
+    assert!(default_env.for_env(Some("development")).is_none());
+}
+
+#[test]
+fn env_rules_without_file_name() {
+    let with_env = config(&[], &[]).with_env(
+        "production",
+        ConfigOverride {
+            level: Some(Level::Warn),
+            ..Default::default()
+        },
+    );
+    GLOBALS.set(&Globals::new(), || {
+        let visitor = TransformVisitor::new(
+            with_env.for_env(Some("production")).unwrap(),
+            None,
+            Mark::new(),
+        );
+        // the host passed no file name, the environment rule still applies
+        assert!(visitor.config.removes_method("log"));
+        assert!(!visitor.config.removes_method("warn"));
+    });
+}
+
+#[test]
+#[should_panic(expected = "set defaultEnv")]
+fn env_rules_without_env() {
+    config(&[], &[])
+        .with_env("production", ConfigOverride::default())
+        .for_env(None);
+}
+
+#[test]
+fn validate_overlap() {
+    assert!(config(&["log"], &["log"]).validate().is_err());
+    assert!(config(&["log"], &["l*"]).validate().is_err());
+    assert!(config(&["time*"], &["time*"]).validate().is_err());
+    assert!(config(&["*"], &["warn"]).validate().is_ok());
+    assert!(config(&["log"], &["warn"]).validate().is_ok());
+    // overrides are checked together with what they inherit
+    let excludes_log = ConfigOverride {
+        files: files(&["src/**"]),
+        excludes: Some(files(&["log"])),
+        ..Default::default()
+    };
+    assert!(config(&["log"], &[])
+        .with_override(excludes_log.clone())
+        .validate()
+        .is_err());
+    assert!(config(&[], &[])
+        .with_override(excludes_log)
+        .validate()
+        .is_ok());
+}
+
+#[test]
+#[should_panic(expected = "overlaps")]
+fn parse_config_rejects_env_overlap() {
+    parse_config(r#"{ "includes": ["log"], "env": { "production": { "excludes": ["log"] } } }"#);
+}
+
+#[test]
+fn parse_config_without_file() {
+    let config = parse_config(r#"{ "level": "warn" }"#);
+    assert!(config.file().is_empty());
+    assert!(config.file().matches("src/index.js"));
+}
+
+#[test]
+#[should_panic(expected = "overlaps")]
+fn parse_config_rejects_overlap() {
+    parse_config(r#"{ "includes": ["log"], "excludes": ["log"], "file": {} }"#);
+}
+
+#[test]
+#[should_panic(expected = "invalid file rule")]
+fn parse_config_rejects_invalid_file_rule() {
+    parse_config(r#"{ "file": { "includes": ["src/[a.js"] } }"#);
+}
+
+#[test]
+#[should_panic(expected = "override without files")]
+fn parse_config_rejects_override_without_files() {
+    parse_config(r#"{ "overrides": [{ "files": [], "level": "error" }] }"#);
+}
+
+#[test]
+#[should_panic(expected = "invalid callee")]
+fn parse_config_rejects_invalid_callee() {
+    parse_config(r#"{ "callees": ["logger..debug"] }"#);
+}
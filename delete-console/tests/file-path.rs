@@ -1,32 +1,172 @@
-use delete_console::{file_check, Config, ConfigFile};
+use delete_console::{file_check, normalize_path, relative_path, ConfigFile};
 
 #[test]
-fn file_path_include() {
-    let test_config = Config::new(
-        vec![],
-        vec![],
+fn file_check_rules() {
+    let cases = [
+        // `*` stays within one directory
+        ("*.js", "input.js", true),
+        ("*.js", "tests/input.js", false),
+        ("tests/*.js", "tests/input.js", true),
+        ("tests/*.js", "tests/fixture/input.js", false),
+        ("*/tests/*.js", "pkg/tests/input.js", true),
+        // `**` matches zero or more directories
+        ("tests/**/*.js", "tests/input.js", true),
+        ("tests/**/*.js", "tests/fixture/console/input.js", true),
+        ("tests/**/*.js", "src/tests/input.js", false),
+        ("**/tests/**/*.js", "src/tests/fixture/input.js", true),
+        (
+            "**/tests/**/*.js",
+            "/Users/liushuai/Desktop/study/rust/swc_plugin/delete-console/tests/input.js",
+            true,
+        ),
+        ("**/tests/**/*.js", "src/test/input.js", false),
+        ("**", "src/a/b/c.ts", true),
+        // extensions
+        ("src/**/*.js", "src/index.ts", false),
+        ("src/**/*.js", "src/index.js.map", false),
+        // `?`
+        ("src/?.js", "src/a.js", true),
+        ("src/?.js", "src/ab.js", false),
+        // character classes
+        ("src/[ab].js", "src/a.js", true),
+        ("src/[ab].js", "src/c.js", false),
+        ("src/[!ab].js", "src/c.js", true),
+        ("src/[a-c].js", "src/b.js", true),
+        // `{a,b}` alternation, including nested groups
+        ("src/**/*.{js,ts}", "src/a/index.ts", true),
+        ("src/**/*.{js,ts}", "src/a/index.tsx", false),
+        ("{src,lib}/*.js", "lib/index.js", true),
+        ("{src,lib}/*.js", "test/index.js", false),
+        ("src/*.{js,{ts,tsx}}", "src/index.tsx", true),
+        ("src/{a,}index.js", "src/index.js", true),
+        // plain paths
+        ("src/index.js", "src/index.js", true),
+        ("src/index.js", "src/index.ts", false),
+        // rules written relative to the project or with Windows separators
+        ("./src/**/*.js", "src/index.js", true),
+        ("src\\**\\*.js", "src/a/index.js", true),
+    ];
+    for (rule, path, expected) in cases {
+        assert_eq!(
+            file_check(rule, path),
+            expected,
+            "{} against {}",
+            rule,
+            path
+        );
+    }
+}
+
+#[test]
+fn config_file_matches() {
+    let file = |includes: &[&str], excludes: &[&str]| {
         ConfigFile::new(
-            // vec!["**/tests/*.js".into()],
-            vec![
-                // "**/tests/**/*.js".into(),
-                // "**/tests/*.js".into(),
-                // "tests/**/*.js".into(),
-                // "*/tests/**/*.js".into(),
-                "**/tests/**/*.js".into(),
-            ],
-            vec![],
-        ),
-    );
-    let test_file_path =
-        "/Users/liushuai/Desktop/study/rust/swc_plugin/delete-console/tests/input.js";
-    for rule_includes in test_config.file().includes.clone() {
-        println!(
-            "file check rule: {}   reslut------> {}",
-            rule_includes,
-            file_check(
-                rule_includes.as_ref().to_string(),
-                test_file_path.to_string()
-            )
+            includes.iter().map(|&rule| rule.into()).collect(),
+            excludes.iter().map(|&rule| rule.into()).collect(),
         )
+    };
+    let cases = [
+        (file(&["src/**"], &[]), "src/index.js", true),
+        (file(&["src/**"], &[]), "lib/index.js", false),
+        // excludes take precedence over includes
+        (
+            file(&["src/**"], &["src/vendor/**"]),
+            "src/vendor/a.js",
+            false,
+        ),
+        (file(&["src/**"], &["src/vendor/**"]), "src/index.js", true),
+        (
+            file(&["src/**", "src/vendor/**"], &["**/vendor/**"]),
+            "src/vendor/a.js",
+            false,
+        ),
+        // negated rules carve files out of the other rules of the same list
+        (
+            file(&["src/**", "!src/**/*.test.js"], &[]),
+            "src/a.test.js",
+            false,
+        ),
+        (
+            file(&["src/**", "!src/**/*.test.js"], &[]),
+            "src/a.js",
+            true,
+        ),
+        (
+            file(&["!src/**/*.test.js", "src/**"], &[]),
+            "src/a.test.js",
+            false,
+        ),
+        (
+            file(&["src/**"], &["src/vendor/**", "!src/vendor/own/**"]),
+            "src/vendor/own/a.js",
+            true,
+        ),
+        (
+            file(&["src/**"], &["src/vendor/**", "!src/vendor/own/**"]),
+            "src/vendor/b.js",
+            false,
+        ),
+        // without plain includes every file is included
+        (file(&[], &[]), "lib/index.js", true),
+        (file(&[], &["lib/**"]), "lib/index.js", false),
+        (file(&["!src/**"], &[]), "lib/index.js", true),
+        (file(&["!src/**"], &[]), "src/index.js", false),
+    ];
+    for (file, path, expected) in cases {
+        assert_eq!(file.matches(path), expected, "{} with {:?}", path, file);
+    }
+}
+
+#[test]
+fn normalize_paths() {
+    let cases = [
+        ("src/index.js", "src/index.js"),
+        ("./src/./index.js", "src/index.js"),
+        ("src/a/../index.js", "src/index.js"),
+        ("src//a/index.js", "src/a/index.js"),
+        ("../lib/index.js", "../lib/index.js"),
+        ("/project/src/../../index.js", "/index.js"),
+        ("/../index.js", "/index.js"),
+        ("src\\a\\index.js", "src/a/index.js"),
+        ("C:\\project\\src\\index.js", "c:/project/src/index.js"),
+        ("C:/project/src/../index.js", "c:/project/index.js"),
+    ];
+    for (path, expected) in cases {
+        assert_eq!(normalize_path(path), expected, "{}", path);
+    }
+}
+
+#[test]
+fn relative_paths() {
+    let cases = [
+        ("/project/src/index.js", Some("/project"), "src/index.js"),
+        ("/project/src/index.js", Some("/project/"), "src/index.js"),
+        (
+            "/project/./src/a/../index.js",
+            Some("/project"),
+            "src/index.js",
+        ),
+        (
+            "C:\\project\\src\\index.js",
+            Some("c:\\project"),
+            "src/index.js",
+        ),
+        // files outside the project keep their absolute path
+        (
+            "/other/src/index.js",
+            Some("/project"),
+            "/other/src/index.js",
+        ),
+        (
+            "/project-b/index.js",
+            Some("/project"),
+            "/project-b/index.js",
+        ),
+        ("/project/src/index.js", None, "/project/src/index.js"),
+        // relative file names are already relative to the project
+        ("./src/index.js", Some("/project"), "src/index.js"),
+    ];
+    for (path, cwd, expected) in cases {
+        assert_eq!(relative_path(path, cwd), expected, "{} in {:?}", path, cwd);
     }
 }
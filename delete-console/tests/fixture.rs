@@ -1,22 +1,76 @@
-use std::path::PathBuf;
+use std::{fs, path::PathBuf};
 
-use delete_console::{Config, ConfigFile, TransformVisitor};
-use swc_core::ecma::{transforms::testing::test_fixture, visit::as_folder};
+use delete_console::{parse_config, Config, ConfigFile, TransformVisitor};
+use swc_core::{
+    common::{chain, Mark},
+    ecma::{
+        ast::{Module, Program, Script},
+        transforms::{base::resolver, testing::test_fixture},
+        visit::{as_folder, Fold, FoldWith},
+    },
+};
+
+/// Runs the plugin only when the build environment has a rule, like
+/// `process_transform` does.
+struct EnvGate<V>(Option<V>);
+
+impl<V: Fold> Fold for EnvGate<V> {
+    fn fold_program(&mut self, n: Program) -> Program {
+        match &mut self.0 {
+            Some(visitor) => n.fold_with(visitor),
+            None => n,
+        }
+    }
+
+    fn fold_module(&mut self, n: Module) -> Module {
+        match &mut self.0 {
+            Some(visitor) => n.fold_with(visitor),
+            None => n,
+        }
+    }
+
+    fn fold_script(&mut self, n: Script) -> Script {
+        match &mut self.0 {
+            Some(visitor) => n.fold_with(visitor),
+            None => n,
+        }
+    }
+}
 
 #[testing::fixture("tests/fixture/**/input.js")]
 fn fixture(input: PathBuf) {
-    let output = input.parent().unwrap().join("output.js");
+    let dir = input.parent().unwrap();
+    let output = dir.join("output.js");
+    // A config.json next to input.js overrides the default config.
+    let config = fs::read_to_string(dir.join("config.json"))
+        .map(|config| parse_config(&config))
+        .unwrap_or_else(|_| {
+            Config::new(
+                vec![],
+                vec!["warn".into()],
+                ConfigFile::new(vec!["**/tests/**/*.js".into()], vec![]),
+            )
+        });
+    // An env file next to input.js is the build environment, which may leave
+    // the file untouched.
+    let env = fs::read_to_string(dir.join("env"))
+        .map(|env| env.trim().to_string())
+        .ok();
+    let env_config = config.for_env(env.as_deref());
+    let enabled = env_config.is_some();
+    let config = env_config.unwrap_or(config);
     test_fixture(
         Default::default(),
         &|_t| {
-            as_folder(TransformVisitor::new(
-                Config::new(
-                    vec![],
-                    vec!["warn".into()],
-                    ConfigFile::new(vec!["**/tests/**/*.js".into()], vec![]),
-                ),
-                Option::Some((*input.to_str().unwrap()).to_string()),
-            ))
+            let unresolved_mark = Mark::new();
+            chain!(
+                resolver(unresolved_mark, Mark::new(), false),
+                EnvGate(enabled.then(|| as_folder(TransformVisitor::new(
+                    config.clone(),
+                    Option::Some((*input.to_str().unwrap()).to_string()),
+                    unresolved_mark,
+                ))))
+            )
         },
         &input,
         &output,
@@ -0,0 +1,24 @@
+const { log, warn, info: print } = console;
+const c = console;
+const debug = console.debug.bind(console);
+log("a");
+warn("b");
+print("c");
+c.error("d");
+debug("e");
+window.console.log("f");
+globalThis.console.warn("g");
+console['log']("h");
+console.log.call(console, "i");
+console.info.apply(console, args);
+const bound = console.log.bind(console);
+function scoped(console) {
+    console.log("kept");
+}
+{
+    const console = logger;
+    console.log("kept");
+}
+let mutable = console.log;
+mutable = other;
+mutable("kept");
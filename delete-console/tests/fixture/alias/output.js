@@ -0,0 +1,15 @@
+const { log , warn , info: print  } = console;
+const c = console;
+const debug = ()=>{};
+warn("b");
+globalThis.console.warn("g");
+const bound = ()=>{};
+function scoped(console1) {
+    console1.log("kept");
+}
+{
+    const console1 = logger;
+    console1.log("kept");
+}let mutable = ()=>{};
+mutable = other;
+mutable("kept");
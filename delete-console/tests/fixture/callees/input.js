@@ -0,0 +1,14 @@
+function save(user) {
+    debugger;
+    performance.mark("save-start");
+    logger.debug("saving", user, counter++);
+    logger.info("saving");
+    assert.equal(user.id, 1);
+    assert.deep.equal(user, {});
+    assert(user);
+    myDebug(user);
+    if (!user) alert("missing user");
+    const ok = user.valid && myDebug(user);
+    this.log(user);
+    return ok;
+}
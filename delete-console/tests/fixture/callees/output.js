@@ -0,0 +1,9 @@
+function save(user) {
+    counter++;
+    logger.info("saving");
+    assert.deep.equal(user, {});
+    assert(user);
+    if (!user) ;
+    const ok = user.valid && void 0;
+    return ok;
+}
@@ -0,0 +1,20 @@
+import assert from "assert";
+import { myDebug } from "./debug";
+const logger = require("./logger");
+
+function save(user) {
+    assert.equal(user.id, 1);
+    myDebug(user);
+    logger.debug("saving");
+}
+
+function retry(user, logger, myDebug) {
+    function alert(message) {
+        show(message);
+    }
+    const assert = check(user);
+    assert.equal(user.id, 1);
+    myDebug(user);
+    alert("missing user");
+    logger.debug("retrying");
+}
@@ -0,0 +1,2 @@
+console.warn("warn");
+console.error("error");
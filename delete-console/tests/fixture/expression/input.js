@@ -0,0 +1,19 @@
+cond && console.log(x);
+cond || console.info(x);
+a ? console.log(1) : b();
+a ? b() : console.log(1);
+a ? console.log(1) : console.error(2);
+const value = (console.log(a), b);
+const method = (console.log(a), obj.fn)();
+const flag = cond && console.log(x);
+const onClick = () => console.log(e);
+promise.then(console.log).catch(console.error);
+console.warn(x) || fallback();
+if (debug) console.debug(state);
+cond && console.warn(x);
+console.log?.(x);
+console?.log(x);
+cond && console.log?.(x);
+const optional = console.log?.(x);
+[function () { console.log(x); }];
+setTimeout(function () { console.log(x); }), void 0;
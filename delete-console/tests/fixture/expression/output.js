@@ -0,0 +1,19 @@
+cond;
+cond;
+a || b();
+a && b();
+a;
+const value = b;
+const method = (0, obj.fn)();
+const flag = cond && void 0;
+const onClick = ()=>{};
+promise.then(()=>{}).catch(()=>{});
+console.warn(x) || fallback();
+if (debug) ;
+cond && console.warn(x);
+cond;
+const optional = void 0;
+[
+    function() {}
+];
+setTimeout(function() {}), void 0;
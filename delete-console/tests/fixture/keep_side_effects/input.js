@@ -0,0 +1,10 @@
+async function run() {
+    console.log(counter++, await save());
+    console.log("static", value, obj.prop, obj["key"], `text`, !flag, () => {});
+    console.info(...items);
+    console.info(...collect());
+    const result = console.log(update());
+    ready && console.log(report());
+    console.log(`${user}`, console.error(fail()));
+    console.warn(kept());
+}
@@ -0,0 +1,13 @@
+async function run() {
+    counter++, await save();
+    [
+        ...items
+    ];
+    [
+        ...collect()
+    ];
+    const result = (update(), void 0);
+    ready && report();
+    `${user}`, fail();
+    console.warn(kept());
+}
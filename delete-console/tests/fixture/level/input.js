@@ -0,0 +1,7 @@
+console.debug("debug");
+console.trace("trace");
+console.log("log");
+console.table(rows);
+console.info("info");
+console.warn("warn");
+console.error("error");
@@ -0,0 +1,3 @@
+console.log("log");
+console.table([1, 2]);
+console.error("error");
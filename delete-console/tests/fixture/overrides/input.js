@@ -0,0 +1,4 @@
+console.debug("debug");
+console.log("log");
+console.warn("warn");
+console.error("error");
@@ -0,0 +1 @@
+console.error("error");
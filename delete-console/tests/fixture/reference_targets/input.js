@@ -0,0 +1,10 @@
+console.log = wrap(console.log);
+(console.info) = noop;
+console.debug ||= noop;
+console.table++;
+[console.log, { handler: console.info }] = handlers;
+for (console.log in listeners) {}
+delete console.debug;
+if (typeof console.log === "function") {
+    promise.then(console.log);
+}
@@ -0,0 +1,4 @@
+const { log  } = console;
+log("kept");
+console.log("kept");
+const handler = ()=>console.warn("kept");
@@ -0,0 +1,7 @@
+console.time("load");
+console.timeLog("load");
+console.timeEnd("load");
+console.group("section");
+console.groupEnd();
+console.log("log");
+console.info("info");
@@ -0,0 +1,2 @@
+console.timeLog("load");
+console.info("info");